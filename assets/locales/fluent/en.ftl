@@ -0,0 +1,8 @@
+fluent-hello = Hello from Fluent, { $name }!
+fluent-cats =
+    { $count ->
+        [one] You have { $count } cat
+       *[other] You have { $count } cats
+    }
+menu = Menu
+    .title = Fluent menu
@@ -0,0 +1,4 @@
+fluent-hello = { $name }さん、Fluentからこんにちは！
+fluent-cats = 猫を{ $count }匹飼っています
+menu = メニュー
+    .title = Fluentメニュー
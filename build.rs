@@ -8,6 +8,8 @@ use std::{
 const ASSET_PATH_VAR: &str = "BEVY_ASSET_PATH";
 const OUTPUT_FILE_NAME: &str = "bevy_simple_i18n.rs";
 const ALLOWED_EXTENSIONS: &[&str] = &["otf", "ttf"];
+const LOCALE_EXTENSIONS: &[&str] = &["json", "yml", "yaml", "toml"];
+const FLUENT_EXTENSION: &str = "ftl";
 
 fn main() {
     cargo_emit::rerun_if_env_changed!(ASSET_PATH_VAR);
@@ -15,6 +17,7 @@ fn main() {
     let out_dir = env::var_os("OUT_DIR").unwrap();
 
     let mut files = Vec::new();
+    let mut locale_files = Vec::new();
 
     let mut marker_file = File::create(Path::new(&out_dir).join(OUTPUT_FILE_NAME)).unwrap();
 
@@ -71,19 +74,8 @@ fn main() {
         cargo_emit::rerun_if_changed!(dir.to_string_lossy());
         // cargo_emit::warning!("Asset folder found: {}", dir.to_string_lossy());
 
-        marker_file
-            .write_all(
-                format!(
-                    r#"rust_i18n::i18n!("{}");
-
-"#,
-                    dir.to_string_lossy().replace('\\', "/"),
-                )
-                .as_bytes(),
-            )
-            .unwrap();
-
         let building_for_wasm = std::env::var("CARGO_CFG_TARGET_ARCH") == Ok("wasm32".to_string());
+        let fluent_enabled = env::var("CARGO_FEATURE_FLUENT").is_ok();
 
         visit_dirs(&dir)
             .iter()
@@ -96,6 +88,12 @@ fn main() {
                 }
                 cargo_emit::rerun_if_changed!(full_path.to_string_lossy());
                 if let Some(ext) = full_path.extension().and_then(|e| e.to_str()) {
+                    if LOCALE_EXTENSIONS.contains(&ext)
+                        || (fluent_enabled && ext == FLUENT_EXTENSION)
+                    {
+                        // Locale files are loaded at runtime through the asset server
+                        locale_files.push(string_path.clone());
+                    }
                     if ALLOWED_EXTENSIONS.contains(&ext) {
                         // Extract filename without extension
                         let locale = path.file_stem().unwrap().to_string_lossy().into_owned();
@@ -132,6 +130,8 @@ fn main() {
         // panic!("No asset folder found");
     }
 
+    locale_files.sort();
+
     let mut families: Vec<FontFamily> = Vec::new();
     for asset in files.iter() {
         if let Some(family) = families.iter_mut().find(|f| f.folder == asset.family) {
@@ -162,7 +162,9 @@ pub(crate) struct FontFamily {{
 }}
 
 {}
-pub(crate) const FONT_FAMILIES: &'static [FontFamily] = &[{}];
+pub(crate) const FONT_FAMILIES: &[FontFamily] = &[{}];
+
+pub(crate) const LOCALE_FILES: &[&str] = &{:?};
 "#,
                 families
                     .iter()
@@ -173,7 +175,8 @@ pub(crate) const FONT_FAMILIES: &'static [FontFamily] = &[{}];
                     .iter()
                     .map(|s| s.push_const())
                     .collect::<Vec<_>>()
-                    .join(", ")
+                    .join(", "),
+                locale_files,
             )
             .as_bytes(),
         )
@@ -211,7 +214,7 @@ impl FontFamily {
     }
 
     fn push_const(&self) -> String {
-        format!("{}", self.snake_case().to_uppercase())
+        self.snake_case().to_uppercase()
     }
 
     fn snake_case(&self) -> String {
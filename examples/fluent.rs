@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use bevy_simple_i18n::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Add the base plugin, `.ftl` files are picked up when the `fluent` feature is enabled
+        .add_plugins(I18nPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands
+        .spawn(Node {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            display: Display::Flex,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(20.),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            // Message from `assets/locales/fluent/{locale}.ftl` with a variable
+            parent.spawn((
+                I18nText::new("fluent-hello").with_arg("name", "Bevy User"),
+                I18nFont::new("NotoSans"),
+            ));
+
+            // Number arguments can be used in Fluent selectors
+            parent.spawn((
+                I18nText::new("fluent-cats").with_num_arg("count", 1),
+                I18nFont::new("NotoSans"),
+            ));
+            parent.spawn((
+                I18nText::new("fluent-cats").with_num_arg("count", 3),
+                I18nFont::new("NotoSans"),
+            ));
+
+            // Attributes of a message are accessed with a `.`
+            parent.spawn((
+                I18nText::new("menu.title").with_locale("ja"),
+                I18nFont::new("NotoSans"),
+            ));
+
+            // Keys that aren't in the Fluent files fall back to the JSON/YAML/TOML translations
+            parent.spawn((
+                I18nText::new("messages.hello").with_arg("name", "Bevy User"),
+                I18nFont::new("NotoSans"),
+            ));
+        });
+}
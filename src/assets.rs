@@ -0,0 +1,341 @@
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, LoadContext},
+    reflect::TypePath,
+    utils::hashbrown::HashMap,
+};
+use serde_json::Value;
+
+/// Extensions of the locale files that can be loaded by the [LocaleFileLoader]
+#[cfg(not(feature = "fluent"))]
+pub(crate) const LOCALE_FILE_EXTENSIONS: &[&str] = &["json", "yml", "yaml", "toml"];
+#[cfg(feature = "fluent")]
+pub(crate) const LOCALE_FILE_EXTENSIONS: &[&str] = &["json", "yml", "yaml", "toml", "ftl"];
+
+/// Asset containing the translations parsed from a single locale file
+///
+/// Both the v1 (one locale per file) and v2 (all locales in one file) formats of `rust-i18n` are supported
+///
+/// With the `fluent` feature enabled, [Fluent](https://projectfluent.org) `.ftl` files are supported as well,
+/// their locale is taken from the file name (`en-US.ftl`) or from the parent folder (`en-US/main.ftl`)
+///
+/// Every loaded [LocaleFile] is passed to the [crate::backend::TranslationBackend] of the [crate::resources::I18n] resource,
+/// so additional files (patches, DLC languages, etc.) can be loaded at any time
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # #[derive(Resource)]
+/// # struct DlcLocales(Handle<LocaleFile>);
+/// fn load_dlc(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let handle: Handle<LocaleFile> = asset_server.load("dlc/locales/fr.json");
+///     // Keep the handle alive for as long as the translations should be available
+///     commands.insert_resource(DlcLocales(handle));
+/// }
+/// ```
+#[derive(Asset, TypePath, Debug, Default, Clone)]
+pub struct LocaleFile {
+    /// Flattened translations, keyed by locale and then by translation key
+    pub(crate) translations: HashMap<String, HashMap<String, String>>,
+    /// Fluent sources, keyed by locale
+    #[cfg(feature = "fluent")]
+    pub(crate) fluent: HashMap<String, String>,
+}
+
+impl LocaleFile {
+    /// Parses the content of a locale file with the provided extension
+    ///
+    /// `name` is the file name without its extension, the part after the last `.` is used as the
+    /// locale for the v1 format (`en.json`, `app.ja.yml`) and the whole name for Fluent files
+    pub fn parse(content: &str, ext: &str, name: &str) -> Result<Self, LocaleFileError> {
+        let mut value = match ext {
+            #[cfg(feature = "fluent")]
+            "ftl" => {
+                return Ok(Self {
+                    fluent: HashMap::from([(name.to_string(), content.to_string())]),
+                    ..Default::default()
+                })
+            }
+            "yml" | "yaml" => serde_yaml::from_str::<Value>(content)?,
+            "json" => serde_json::from_str::<Value>(content)?,
+            "toml" => toml::from_str::<Value>(content)?,
+            _ => return Err(LocaleFileError::UnsupportedExtension(ext.to_string())),
+        };
+
+        let mut translations: HashMap<String, HashMap<String, String>> = HashMap::new();
+        match value.get("_version").and_then(Value::as_u64).unwrap_or(1) {
+            2 => parse_v2("", &value, &mut translations),
+            _ => {
+                // The format version is metadata, not a translation
+                if let Value::Object(map) = &mut value {
+                    map.remove("_version");
+                }
+                let locale = name.rsplit('.').next().unwrap_or(name);
+                flatten_keys(
+                    "",
+                    &value,
+                    translations.entry(locale.to_string()).or_default(),
+                );
+            }
+        }
+
+        Ok(Self {
+            translations,
+            #[cfg(feature = "fluent")]
+            fluent: HashMap::new(),
+        })
+    }
+
+    /// Returns the locales that have translations in this file
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        #[cfg(feature = "fluent")]
+        let fluent = self.fluent.keys();
+        #[cfg(not(feature = "fluent"))]
+        let fluent = std::iter::empty();
+        self.translations.keys().chain(fluent).map(String::as_str)
+    }
+
+    /// Returns the translation for the provided locale and key, if any
+    pub fn get(&self, locale: &str, key: &str) -> Option<&str> {
+        self.translations
+            .get(locale)
+            .and_then(|trs| trs.get(key))
+            .map(String::as_str)
+    }
+}
+
+/// Locale file format v2, the values of every key are maps of `locale: text`
+///
+/// ```yml
+/// _version: 2
+/// hello:
+///   en: Hello world
+///   ja: こんにちは世界
+/// ```
+fn parse_v2(prefix: &str, value: &Value, trs: &mut HashMap<String, HashMap<String, String>>) {
+    let Value::Object(messages) = value else {
+        return;
+    };
+    for (key, value) in messages {
+        let Value::Object(sub_messages) = value else {
+            continue;
+        };
+        let key = join_keys(prefix, key);
+        let mut nested = false;
+        for (locale, text) in sub_messages {
+            match text {
+                Value::String(text) => {
+                    trs.entry(locale.clone())
+                        .or_default()
+                        .insert(key.clone(), text.clone());
+                }
+                Value::Object(_) => nested = true,
+                _ => {}
+            }
+        }
+        // Nested keys, `welcome: { sub: { en: ..., ja: ... } }` becomes `welcome.sub`
+        if nested {
+            parse_v2(&key, value, trs);
+        }
+    }
+}
+
+/// Flattens nested keys into dot separated keys, `{ "a": { "b": "c" } }` becomes `{ "a.b": "c" }`
+fn flatten_keys(prefix: &str, value: &Value, trs: &mut HashMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten_keys(&join_keys(prefix, key), value, trs);
+            }
+        }
+        Value::String(s) => {
+            trs.insert(prefix.to_string(), s.clone());
+        }
+        Value::Bool(b) => {
+            trs.insert(prefix.to_string(), b.to_string());
+        }
+        Value::Number(n) => {
+            trs.insert(prefix.to_string(), n.to_string());
+        }
+        Value::Null | Value::Array(_) => {
+            trs.insert(prefix.to_string(), String::new());
+        }
+    }
+}
+
+fn join_keys(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// Errors that can occur while loading a [LocaleFile]
+#[derive(Debug, thiserror::Error)]
+pub enum LocaleFileError {
+    #[error("Could not read locale file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Locale file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("Invalid JSON format: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid YAML format: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("Invalid TOML format: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Unsupported locale file extension: {0}")]
+    UnsupportedExtension(String),
+}
+
+/// Asset loader for JSON, YAML and TOML [LocaleFile]s
+#[derive(Debug, Default)]
+pub struct LocaleFileLoader;
+
+impl AssetLoader for LocaleFileLoader {
+    type Asset = LocaleFile;
+    type Settings = ();
+    type Error = LocaleFileError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let content = String::from_utf8(bytes)?;
+
+        let path = load_context.path();
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        #[cfg(feature = "fluent")]
+        let name = fluent_locale(path, ext, name);
+
+        bevy::log::debug!("Loading locale file: {}", path.display());
+        LocaleFile::parse(&content, ext, name)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        LOCALE_FILE_EXTENSIONS
+    }
+}
+
+/// Fluent files are often grouped in a folder per locale, `en-US/main.ftl`, so the folder name is used
+/// when it's a locale, and the file name otherwise, `locales/en-US.ftl`
+#[cfg(feature = "fluent")]
+fn fluent_locale<'a>(path: &'a std::path::Path, ext: &str, name: &'a str) -> &'a str {
+    if ext != "ftl" {
+        return name;
+    }
+    path.parent()
+        .and_then(|p| p.file_name())
+        .and_then(|s| s.to_str())
+        .filter(|folder| is_locale(folder))
+        .unwrap_or(name)
+}
+
+/// Returns `true` for locales with a 2 or 3 letter language, `en` or `en-US`
+///
+/// Longer languages are valid language identifiers but aren't used, so folders like `locales` or
+/// `translations` aren't taken for locales
+#[cfg(feature = "fluent")]
+fn is_locale(name: &str) -> bool {
+    name.parse::<unic_langid::LanguageIdentifier>()
+        .is_ok_and(|langid| matches!(langid.language.as_str().len(), 2 | 3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocaleFile, LocaleFileError};
+
+    #[test]
+    fn v1_files_are_flattened() {
+        let file = LocaleFile::parse(
+            r#"{ "_version": 1, "hello": "Hello", "menu": { "start": "Start", "volume": 5, "on": true } }"#,
+            "json",
+            "app.en",
+        )
+        .unwrap();
+        assert_eq!(file.locales().collect::<Vec<_>>(), ["en"]);
+        assert_eq!(file.get("en", "hello"), Some("Hello"));
+        assert_eq!(file.get("en", "menu.start"), Some("Start"));
+        assert_eq!(file.get("en", "menu.volume"), Some("5"));
+        assert_eq!(file.get("en", "menu.on"), Some("true"));
+        assert_eq!(file.get("en", "_version"), None);
+    }
+
+    #[test]
+    fn v2_files_contain_every_locale() {
+        let file = LocaleFile::parse(
+            "_version: 2\nhello:\n  en: Hello\n  ja: こんにちは\nmenu:\n  start:\n    en: Start\n    ja: スタート\n",
+            "yml",
+            "app",
+        )
+        .unwrap();
+        let mut locales = file.locales().collect::<Vec<_>>();
+        locales.sort();
+        assert_eq!(locales, ["en", "ja"]);
+        assert_eq!(file.get("en", "hello"), Some("Hello"));
+        assert_eq!(file.get("ja", "hello"), Some("こんにちは"));
+        assert_eq!(file.get("ja", "menu.start"), Some("スタート"));
+    }
+
+    #[test]
+    fn every_format_is_parsed() {
+        let toml = LocaleFile::parse("hello = \"Hej\"\n[menu]\nstart = \"Start\"\n", "toml", "sv")
+            .unwrap();
+        assert_eq!(toml.get("sv", "hello"), Some("Hej"));
+        assert_eq!(toml.get("sv", "menu.start"), Some("Start"));
+
+        let yaml = LocaleFile::parse("hello: Hallo\n", "yaml", "de").unwrap();
+        assert_eq!(yaml.get("de", "hello"), Some("Hallo"));
+
+        let v2 = LocaleFile::parse(
+            "_version = 2\n[hello]\nen = \"Hello\"\nfr = \"Bonjour\"\n",
+            "toml",
+            "app",
+        )
+        .unwrap();
+        assert_eq!(v2.get("fr", "hello"), Some("Bonjour"));
+    }
+
+    #[test]
+    fn invalid_files_are_errors() {
+        assert!(matches!(
+            LocaleFile::parse("hello: Hello", "txt", "en"),
+            Err(LocaleFileError::UnsupportedExtension(ext)) if ext == "txt"
+        ));
+        assert!(LocaleFile::parse("{ \"hello\": ", "json", "en").is_err());
+        assert!(LocaleFile::parse("hello = ", "toml", "en").is_err());
+        assert!(LocaleFile::parse("hello: [", "yml", "en").is_err());
+    }
+
+    #[cfg(feature = "fluent")]
+    #[test]
+    fn fluent_locale_prefers_locale_folders() {
+        use super::fluent_locale;
+        use std::path::Path;
+
+        let locale = |path: &str| {
+            let path = Path::new(path);
+            let name = path.file_stem().and_then(|s| s.to_str()).unwrap();
+            let ext = path.extension().and_then(|s| s.to_str()).unwrap();
+            fluent_locale(path, ext, name).to_string()
+        };
+        assert_eq!(locale("locales/en-US/ui.ftl"), "en-US");
+        assert_eq!(locale("locales/en-US/items.ftl"), "en-US");
+        assert_eq!(locale("locales/de/main.ftl"), "de");
+        assert_eq!(locale("locales/en.ftl"), "en");
+        assert_eq!(locale("translations/pt-BR.ftl"), "pt-BR");
+        assert_eq!(locale("locales/en-US/ui.json"), "ui");
+    }
+}
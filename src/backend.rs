@@ -0,0 +1,154 @@
+use bevy::utils::hashbrown::HashMap;
+
+use crate::{assets::LocaleFile, components::InterpolationType};
+
+/// Trait for providing the translations used by `bevy_simple_i18n`
+///
+/// The backend is stored in the [crate::resources::I18n] resource and is used for every lookup,
+/// so translations can come from any source (a database, a server, a custom file format, etc.)
+///
+/// [LocaleFileBackend] is the default implementation, it serves the translations of the loaded [LocaleFile]s
+///
+/// # Example
+///
+/// ```
+/// use bevy::utils::hashbrown::HashMap;
+/// use bevy_simple_i18n::prelude::*;
+///
+/// struct StaticBackend(HashMap<(String, String), String>);
+///
+/// impl TranslationBackend for StaticBackend {
+///     fn translate(&self, locale: &str, key: &str, _args: &[(String, InterpolationType)]) -> Option<String> {
+///         self.0.get(&(locale.to_string(), key.to_string())).cloned()
+///     }
+///
+///     fn locales(&self) -> Vec<String> {
+///         let mut locales: Vec<String> = self.0.keys().map(|(locale, _)| locale.clone()).collect();
+///         locales.sort();
+///         locales.dedup();
+///         locales
+///     }
+/// }
+///
+/// let i18n = I18n::new(StaticBackend(HashMap::from([(
+///     ("en".to_string(), "hello".to_string()),
+///     "Hello world".to_string(),
+/// )])));
+/// assert_eq!(i18n.locales(), ["en"]);
+/// ```
+pub trait TranslationBackend: Send + Sync + 'static {
+    /// Returns the message for the exact locale and key, `None` if it's missing
+    ///
+    /// The returned message may still contain `%{name}` placeholders, they are replaced with the
+    /// interpolation arguments afterward. Locale fallbacks (`en-US` -> `en`) and plural keys
+    /// (`messages.cats.one`) are resolved by the caller, which may call this method several times per lookup
+    fn translate(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &[(String, InterpolationType)],
+    ) -> Option<String>;
+
+    /// Returns the sorted list of locales that have translations
+    fn locales(&self) -> Vec<String>;
+
+    /// Returns the keys that have a translation for the exact locale, used by [crate::validation::validate_locales]
+    ///
+    /// Backends that can't list their keys don't need to implement it
+    fn keys(&self, _locale: &str) -> Vec<String> {
+        vec![]
+    }
+
+    /// Called whenever the current locale is changed with [crate::resources::I18n::set_locale]
+    fn set_locale(&mut self, _locale: &str) {}
+
+    /// Called whenever a [LocaleFile] is added, modified or removed, with every loaded file
+    ///
+    /// Files found by the build script come first, so files loaded by the app override their keys
+    fn load_locale_files(&mut self, _files: &[&LocaleFile]) {}
+}
+
+/// Default [TranslationBackend], serves the translations merged from every loaded [LocaleFile]
+#[derive(Default)]
+pub struct LocaleFileBackend {
+    translations: HashMap<String, HashMap<String, String>>,
+    #[cfg(feature = "fluent")]
+    fluent: crate::fluent::FluentCatalog,
+}
+
+impl LocaleFileBackend {
+    /// Merges the translations of a locale file, overriding existing keys
+    pub fn extend(&mut self, file: &LocaleFile) {
+        for (locale, trs) in file.translations.iter() {
+            self.translations
+                .entry(locale.clone())
+                .or_default()
+                .extend(trs.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        #[cfg(feature = "fluent")]
+        for (locale, source) in file.fluent.iter() {
+            self.fluent.extend(locale, source);
+        }
+    }
+
+    /// Removes every translation
+    pub fn clear(&mut self) {
+        self.translations.clear();
+        #[cfg(feature = "fluent")]
+        self.fluent.clear();
+    }
+}
+
+impl TranslationBackend for LocaleFileBackend {
+    fn translate(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &[(String, InterpolationType)],
+    ) -> Option<String> {
+        #[cfg(feature = "fluent")]
+        if let Some(text) = self.fluent.format(locale, key, args) {
+            return Some(text);
+        }
+        #[cfg(not(feature = "fluent"))]
+        let _ = args;
+
+        self.translations
+            .get(locale)
+            .and_then(|trs| trs.get(key))
+            .cloned()
+    }
+
+    fn locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self.translations.keys().cloned().collect();
+        #[cfg(feature = "fluent")]
+        locales.extend(
+            self.fluent
+                .locales()
+                .filter(|locale| !self.translations.contains_key(*locale))
+                .cloned(),
+        );
+        locales.sort();
+        locales
+    }
+
+    fn keys(&self, locale: &str) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .translations
+            .get(locale)
+            .map(|trs| trs.keys().cloned().collect())
+            .unwrap_or_default();
+        #[cfg(feature = "fluent")]
+        keys.extend(self.fluent.keys(locale).cloned());
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    fn load_locale_files(&mut self, files: &[&LocaleFile]) {
+        self.clear();
+        for file in files {
+            self.extend(file);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    system::{Query, Res, Resource},
+    world::World,
+};
+
+use super::{I18nComponent, InterpolationType};
+
+/// Reads the value of a bound argument from the world, `None` if its source doesn't exist
+type Binding = Arc<dyn Fn(&World, Entity) -> Option<InterpolationType> + Send + Sync>;
+
+/// Marker of the entities whose i18n component has bound arguments, the only ones that are read again
+/// every frame
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub(crate) struct HasBoundArgs;
+
+/// Interpolation arguments bound to a component of the entity or to a resource
+///
+/// They're read again every frame, and the translation is updated whenever one of them changes
+#[derive(Clone, Default)]
+pub(crate) struct BoundArgs(Vec<(String, Binding)>);
+
+impl BoundArgs {
+    /// Returns `true` if no argument is bound
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Binds an argument to a component of the translated entity
+    pub(crate) fn push_component<C: Component, V: Into<InterpolationType>>(
+        &mut self,
+        key: String,
+        read: impl Fn(&C) -> V + Send + Sync + 'static,
+    ) {
+        self.0.push((
+            key,
+            Arc::new(move |world, entity| world.get::<C>(entity).map(|c| read(c).into())),
+        ));
+    }
+
+    /// Binds an argument to a resource
+    pub(crate) fn push_resource<R: Resource, V: Into<InterpolationType>>(
+        &mut self,
+        key: String,
+        read: impl Fn(&R) -> V + Send + Sync + 'static,
+    ) {
+        self.0.push((
+            key,
+            Arc::new(move |world, _| world.get_resource::<R>().map(|r| read(r).into())),
+        ));
+    }
+
+    /// Returns the current values of the bound arguments that are different from the arguments, empty if
+    /// none of them changed
+    ///
+    /// Bound arguments replace the arguments with the same key, and keep their last value while their
+    /// source doesn't exist
+    pub(crate) fn resolve(
+        &self,
+        args: &[(String, InterpolationType)],
+        world: &World,
+        entity: Entity,
+    ) -> Vec<(String, InterpolationType)> {
+        let mut changed: Vec<(String, InterpolationType)> = vec![];
+        for (key, binding) in &self.0 {
+            let Some(value) = binding(world, entity) else {
+                continue;
+            };
+            let current = changed
+                .iter()
+                .chain(args)
+                .find_map(|(k, v)| (k == key).then_some(v));
+            if current != Some(&value) {
+                changed.retain(|(k, _)| k != key);
+                changed.push((key.clone(), value));
+            }
+        }
+        changed
+    }
+}
+
+impl std::fmt::Debug for BoundArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(key, _)| key))
+            .finish()
+    }
+}
+
+/// Entity and changed values of the bound arguments of its component
+type BoundArgUpdate = (Entity, Vec<(String, InterpolationType)>);
+
+/// Values of the bound arguments that changed, read by [read_bound_args] and applied by [apply_bound_args]
+#[derive(Resource)]
+pub(crate) struct BoundArgUpdates<T> {
+    updates: Mutex<Vec<BoundArgUpdate>>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for BoundArgUpdates<T> {
+    fn default() -> Self {
+        Self {
+            updates: Mutex::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Reads the bound arguments of the components from the world, only the changed values are kept
+///
+/// Components and resources of any type can be bound, so it needs to read the whole world, the values
+/// are applied by [apply_bound_args]
+pub(crate) fn read_bound_args<T: I18nComponent + Component>(
+    world: &World,
+    updates: Res<BoundArgUpdates<T>>,
+    query: Query<(Entity, &T), With<HasBoundArgs>>,
+) {
+    let mut updates = updates
+        .updates
+        .lock()
+        .expect("Bound argument updates lock is poisoned");
+    for (entity, component) in query.iter() {
+        let args = component.bound_args(world, entity);
+        if !args.is_empty() {
+            updates.push((entity, args));
+        }
+    }
+}
+
+/// Sets the bound arguments read by [read_bound_args] in place, the other arguments and the key are kept,
+/// and the components are then translated again by their change detection
+pub(crate) fn apply_bound_args<T: I18nComponent + Component>(
+    updates: Res<BoundArgUpdates<T>>,
+    mut query: Query<&mut T, With<HasBoundArgs>>,
+) {
+    let updates = std::mem::take(
+        &mut *updates
+            .updates
+            .lock()
+            .expect("Bound argument updates lock is poisoned"),
+    );
+    for (entity, args) in updates {
+        if let Ok(mut component) = query.get_mut(entity) {
+            component.set_bound_args(args);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{app::App, ecs::change_detection::DetectChanges, ui::widget::Text};
+
+    use super::*;
+    use crate::{
+        assets::LocaleFile,
+        components::I18nText,
+        events::MissingTranslation,
+        plugin::I18nComponentRegistration,
+        resources::{FontManager, I18n},
+    };
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    #[derive(Resource)]
+    struct Level(&'static str);
+
+    fn app() -> App {
+        let mut app = App::new();
+        let mut i18n = I18n::default();
+        let file = LocaleFile::parse(
+            r#"{"hud": "HP %{hp}, level %{level}, %{name}"}"#,
+            "json",
+            "en",
+        )
+        .unwrap();
+        i18n.load_locale_files(&[&file]);
+        app.insert_resource(i18n)
+            .insert_resource(Level("Cave"))
+            .init_resource::<FontManager>()
+            .add_event::<MissingTranslation>()
+            .register_i18n_component::<I18nText>();
+        app
+    }
+
+    fn spawn(app: &mut App) -> Entity {
+        let entity = app
+            .world_mut()
+            .spawn((
+                Health(10),
+                I18nText::new("hud")
+                    .with_arg("name", "Bevy")
+                    .with_bound_arg("hp", |health: &Health| health.0.to_string())
+                    .with_resource_arg("level", |level: &Level| level.0),
+            ))
+            .id();
+        app.update();
+        entity
+    }
+
+    fn text(app: &App, entity: Entity) -> &str {
+        &app.world().get::<Text>(entity).unwrap().0
+    }
+
+    #[test]
+    fn bound_components_update_the_text() {
+        let mut app = app();
+        let entity = spawn(&mut app);
+        assert_eq!(text(&app, entity), "HP 10, level Cave, Bevy");
+
+        app.world_mut().get_mut::<Health>(entity).unwrap().0 = 7;
+        app.update();
+        assert_eq!(text(&app, entity), "HP 7, level Cave, Bevy");
+    }
+
+    #[test]
+    fn bound_resources_update_the_text() {
+        let mut app = app();
+        let entity = spawn(&mut app);
+
+        app.world_mut().resource_mut::<Level>().0 = "Castle";
+        app.update();
+        assert_eq!(text(&app, entity), "HP 10, level Castle, Bevy");
+    }
+
+    #[test]
+    fn unchanged_values_do_not_change_the_component() {
+        let mut app = app();
+        let entity = spawn(&mut app);
+        let last_changed = |app: &App| {
+            app.world()
+                .entity(entity)
+                .get_ref::<I18nText>()
+                .unwrap()
+                .last_changed()
+        };
+        let tick = last_changed(&app);
+
+        // Reassigning the same value marks the source as changed, but not the text component
+        app.world_mut().get_mut::<Health>(entity).unwrap().0 = 10;
+        app.update();
+        assert_eq!(last_changed(&app), tick);
+        assert_eq!(text(&app, entity), "HP 10, level Cave, Bevy");
+    }
+}
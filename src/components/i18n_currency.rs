@@ -0,0 +1,360 @@
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHooks, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    ui::widget::Text,
+};
+use fixed_decimal::{FixedDecimal, Sign};
+use icu_decimal::provider::DecimalSymbolsV1Marker;
+use icu_experimental::dimension::provider::currency::{
+    CurrencyEssentialsV1Marker, PatternSelection, PlaceholderValue,
+};
+use icu_provider::{DataProvider, DataRequest};
+
+use crate::resources::I18n;
+
+use super::{
+    utils::{self, translate_entity},
+    I18nComponent,
+};
+
+/// Currencies whose number of fraction digits isn't 2, from the `digits` of the CLDR supplemental currency
+/// data, which ICU4X doesn't provide yet
+///
+/// They can differ from the ISO 4217 minor units, e.g. `IQD` is displayed without fraction digits
+const FRACTION_DIGITS: &[(&str, u8)] = &[
+    ("AFN", 0),
+    ("ALL", 0),
+    ("BHD", 3),
+    ("BIF", 0),
+    ("BYR", 0),
+    ("CLF", 4),
+    ("CLP", 0),
+    ("DJF", 0),
+    ("GNF", 0),
+    ("IQD", 0),
+    ("IRR", 0),
+    ("ISK", 0),
+    ("JOD", 3),
+    ("JPY", 0),
+    ("KMF", 0),
+    ("KPW", 0),
+    ("KRW", 0),
+    ("KWD", 3),
+    ("LAK", 0),
+    ("LBP", 0),
+    ("LYD", 3),
+    ("MGA", 0),
+    ("MMK", 0),
+    ("OMR", 3),
+    ("PYG", 0),
+    ("RSD", 0),
+    ("RWF", 0),
+    ("SLL", 0),
+    ("SOS", 0),
+    ("STD", 0),
+    ("SYP", 0),
+    ("TND", 3),
+    ("UGX", 0),
+    ("UYI", 0),
+    ("UYW", 4),
+    ("VND", 0),
+    ("VUV", 0),
+    ("XAF", 0),
+    ("XOF", 0),
+    ("XPF", 0),
+    ("YER", 0),
+];
+
+/// Returns the number of fraction digits of a currency, 2 for the other currencies
+fn fraction_digits(code: &str) -> u8 {
+    FRACTION_DIGITS
+        .iter()
+        .find_map(|(c, digits)| (*c == code).then_some(*digits))
+        .unwrap_or(2)
+}
+
+/// Returns the prefix and suffix of the minus sign of the locale, `-` in `en` or `−` in `sv`
+fn minus_sign_affixes(locale: &str, label: impl ToString) -> (String, String) {
+    let data_locale = utils::resolve_locale(locale, label).into();
+    DataProvider::<DecimalSymbolsV1Marker>::load(
+        &icu_decimal::provider::Baked,
+        DataRequest {
+            locale: &data_locale,
+            metadata: Default::default(),
+        },
+    )
+    .and_then(|response| response.take_payload())
+    .map(|payload| {
+        let affixes = &payload.get().minus_sign_affixes;
+        (affixes.prefix.to_string(), affixes.suffix.to_string())
+    })
+    .unwrap_or_else(|err| {
+        bevy::log::error!("Missing decimal symbols for locale {}: {}", locale, err);
+        ("-".to_string(), String::new())
+    })
+}
+
+/// Amount of money in a currency, along with how it's displayed
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyValue {
+    pub(crate) amount: FixedDecimal,
+    pub(crate) code: String,
+    pub(crate) fraction_digits: u8,
+    pub(crate) narrow: bool,
+}
+
+impl CurrencyValue {
+    /// Creates a new currency value
+    ///
+    /// Codes that aren't made of 3 ASCII letters, e.g. `gold`, can't be ISO 4217 currencies, they're
+    /// displayed as they are with 2 fraction digits
+    pub(crate) fn new(amount: f64, code: impl Into<String>) -> Self {
+        let mut code: String = code.into();
+        if code.len() == 3 && code.bytes().all(|b| b.is_ascii_alphabetic()) {
+            code.make_ascii_uppercase();
+        } else {
+            bevy::log::warn!(
+                "Invalid ISO 4217 currency code: {}, it is displayed as is",
+                code
+            );
+        }
+        Self {
+            amount: utils::f64_to_fd(amount),
+            fraction_digits: fraction_digits(&code),
+            code,
+            narrow: false,
+        }
+    }
+
+    /// Formats the amount for the provided locale, with the currency symbol and its placement
+    /// taken from the CLDR data of the locale
+    pub(crate) fn format_to_string(&self, locale: &str) -> String {
+        let position = -i16::from(self.fraction_digits);
+        let mut amount = self.amount.clone().with_sign(Sign::None);
+        amount.half_even(position);
+        amount.pad_end(position);
+        let number = utils::get_formatter(locale, self).format_to_string(&amount);
+        // Like in the CLDR patterns, negative amounts are the positive pattern with the minus sign of the locale
+        let (sign, sign_suffix) = if self.amount.sign() == Sign::Negative && !amount.is_zero() {
+            minus_sign_affixes(locale, self)
+        } else {
+            Default::default()
+        };
+
+        let data_locale = utils::resolve_locale(locale, self).into();
+        let payload = DataProvider::<CurrencyEssentialsV1Marker>::load(
+            &icu_experimental::provider::Baked,
+            DataRequest {
+                locale: &data_locale,
+                metadata: Default::default(),
+            },
+        )
+        .and_then(|response| response.take_payload());
+        let payload = match payload {
+            Ok(payload) => payload,
+            Err(err) => {
+                bevy::log::error!("Missing currency data for locale {}: {}", locale, err);
+                return format!("{}{} {}{}", sign, number, self.code, sign_suffix);
+            }
+        };
+        let essentials = payload.get();
+
+        let config = self
+            .code
+            .parse::<tinystr::TinyAsciiStr<3>>()
+            .ok()
+            .and_then(|code| {
+                essentials
+                    .pattern_config_map
+                    .get_copied(&code.to_unvalidated())
+            })
+            .unwrap_or(essentials.default_pattern_config);
+        let (selection, placeholder) = if self.narrow {
+            (
+                config.narrow_pattern_selection,
+                config.narrow_placeholder_value,
+            )
+        } else {
+            (
+                config.short_pattern_selection,
+                config.short_placeholder_value,
+            )
+        };
+        let symbol = match placeholder {
+            Some(PlaceholderValue::Index(index)) => essentials
+                .placeholders
+                .get(index.into())
+                .unwrap_or(self.code.as_str()),
+            Some(PlaceholderValue::ISO) | None => self.code.as_str(),
+        };
+        let pattern = match selection {
+            PatternSelection::Standard => essentials.standard_pattern.as_ref(),
+            PatternSelection::StandardAlphaNextToNumber => {
+                essentials.standard_alpha_next_to_number_pattern.as_ref()
+            }
+        };
+
+        match pattern {
+            Some(pattern) => format!(
+                "{}{}{}",
+                sign,
+                pattern.interpolate((number, symbol)),
+                sign_suffix
+            ),
+            None => format!("{}{} {}{}", sign, number, symbol, sign_suffix),
+        }
+    }
+}
+
+impl Default for CurrencyValue {
+    fn default() -> Self {
+        Self::new(0., "XXX")
+    }
+}
+
+/// Formats as the amount followed by the currency code, `12.5 EUR`
+impl std::fmt::Display for CurrencyValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.code)
+    }
+}
+
+/// Component for spawning localized currency entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the amount formatted for the locale,
+/// with the symbol, placement and fraction digits of the ISO 4217 currency
+///
+/// Codes that aren't ISO 4217 currencies, e.g. `GLD` for in-game gold, are displayed as is with 2 fraction digits
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `$1,234.50` in `en`
+/// world.spawn(I18nCurrency::new(1234.5, "USD"));
+///
+/// // In-game gold, `GLD 500`
+/// world.spawn(I18nCurrency::new(500, "GLD").with_fraction_digits(0));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nCurrency::new(1500, "JPY").with_locale("ja"));
+/// ```
+#[derive(Default, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nCurrency {
+    #[reflect(ignore)]
+    pub(crate) value: CurrencyValue,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nCurrency {
+    type Target = Text;
+
+    fn locale(&self, i18n: &I18n) -> String {
+        self.locale
+            .clone()
+            .unwrap_or_else(|| i18n.current().to_string())
+    }
+
+    fn has_fixed_locale(&self) -> bool {
+        self.locale.is_some()
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.value
+            .format_to_string(&i18n.resolve_locale(&self.locale(i18n)))
+    }
+}
+
+impl I18nCurrency {
+    /// Creates a new `I18nCurrency` component with the provided amount and ISO 4217 currency code
+    ///
+    /// Codes that aren't made of 3 ASCII letters are displayed as they are, and a warning is logged
+    pub fn new(amount: impl Into<f64>, code: impl Into<String>) -> Self {
+        Self {
+            value: CurrencyValue::new(amount.into(), code),
+            locale: None,
+        }
+    }
+
+    /// Set the number of fraction digits, overriding the minor unit of the currency
+    pub fn with_fraction_digits(mut self, digits: u8) -> Self {
+        self.value.fraction_digits = digits;
+        self
+    }
+
+    /// Use the narrow symbol of the currency, `$` instead of `US$` in most locales
+    pub fn with_narrow_symbol(mut self) -> Self {
+        self.value.narrow = true;
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+}
+
+impl Component for I18nCurrency {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {
+        _hooks.on_add(|mut world, entity, _| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            debug!("Adding i18n currency: {}", val.value);
+            let translated = translate_entity(&mut world, entity, &val);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(amount: f64, code: &str, locale: &str) -> String {
+        CurrencyValue::new(amount, code).format_to_string(locale)
+    }
+
+    #[test]
+    fn amounts_are_rounded_to_the_fraction_digits_of_the_currency() {
+        assert_eq!(format(1234.5, "USD", "en"), "$1,234.50");
+        assert_eq!(format(1234.5, "JPY", "en"), "¥1,234");
+        assert_eq!(format(1.2345, "KWD", "en"), "KWD\u{a0}1.234");
+        assert_eq!(format(1234.5, "IQD", "en"), "IQD\u{a0}1,234");
+    }
+
+    #[test]
+    fn negative_amounts_use_the_minus_sign_of_the_locale() {
+        assert_eq!(format(-12.5, "USD", "en"), "-$12.50");
+        assert_eq!(format(-12.5, "EUR", "sv"), "−12,50\u{a0}€");
+        assert_eq!(format(-0.001, "USD", "en"), "$0.00");
+    }
+
+    #[test]
+    fn invalid_codes_are_displayed_as_is() {
+        assert_eq!(CurrencyValue::new(1., "usd").code, "USD");
+        assert_eq!(CurrencyValue::new(500., "gold").code, "gold");
+        assert!(format(500., "gold", "en").contains("gold"));
+        assert!(format(500., "gold", "en").contains("500.00"));
+    }
+}
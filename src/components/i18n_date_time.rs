@@ -0,0 +1,330 @@
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHooks, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    ui::widget::Text,
+};
+use icu_calendar::{DateTime, Iso};
+use icu_datetime::options::{components, length};
+
+use crate::resources::I18n;
+
+use super::{
+    utils::{self, translate_entity},
+    I18nComponent,
+};
+
+/// Length of the date or time part of a [DateTimeFormat], from the most to the least detailed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum DateTimeLength {
+    /// `Tuesday, January 21, 2020`, the time is displayed like [DateTimeLength::Medium]
+    Full,
+    /// `January 21, 2020`, the time is displayed like [DateTimeLength::Medium]
+    Long,
+    /// `Jan 21, 2020` or `8:25:07 AM`
+    #[default]
+    Medium,
+    /// `1/21/20` or `8:25 AM`
+    Short,
+}
+
+impl DateTimeLength {
+    fn date(self) -> length::Date {
+        match self {
+            DateTimeLength::Full => length::Date::Full,
+            DateTimeLength::Long => length::Date::Long,
+            DateTimeLength::Medium => length::Date::Medium,
+            DateTimeLength::Short => length::Date::Short,
+        }
+    }
+
+    /// The full and long times display the time zone, which the formatted date times don't have, so
+    /// they're displayed as medium times
+    fn time(self) -> length::Time {
+        match self {
+            DateTimeLength::Full | DateTimeLength::Long | DateTimeLength::Medium => {
+                length::Time::Medium
+            }
+            DateTimeLength::Short => length::Time::Short,
+        }
+    }
+}
+
+/// How a date and time is formatted, the exact pattern depends on the locale
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateTimeFormat {
+    /// Only the date
+    Date(DateTimeLength),
+    /// Only the time
+    Time(DateTimeLength),
+    /// The date followed by the time, medium date and short time by default
+    DateTime(DateTimeLength, DateTimeLength),
+    /// The components to display, matched against the patterns of the locale, e.g. `yMMMd`
+    ///
+    /// See [icu_datetime::options::components::Bag]
+    Skeleton(components::Bag),
+}
+
+impl Default for DateTimeFormat {
+    fn default() -> Self {
+        DateTimeFormat::DateTime(DateTimeLength::Medium, DateTimeLength::Short)
+    }
+}
+
+impl DateTimeFormat {
+    pub(crate) fn options(&self) -> icu_datetime::DateTimeFormatterOptions {
+        match self {
+            DateTimeFormat::Date(date) => length::Bag::from_date_style(date.date()).into(),
+            DateTimeFormat::Time(time) => length::Bag::from_time_style(time.time()).into(),
+            DateTimeFormat::DateTime(date, time) => {
+                length::Bag::from_date_time_style(date.date(), time.time()).into()
+            }
+            DateTimeFormat::Skeleton(bag) => (*bag).into(),
+        }
+    }
+}
+
+/// Date and time to format, along with its [DateTimeFormat]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateTimeValue {
+    pub(crate) datetime: DateTime<Iso>,
+    pub(crate) format: DateTimeFormat,
+}
+
+impl DateTimeValue {
+    pub(crate) fn new(datetime: DateTime<Iso>, format: DateTimeFormat) -> Self {
+        Self { datetime, format }
+    }
+
+    /// Formats the date and time for the provided locale, or as ISO 8601 if the format isn't supported
+    pub(crate) fn format_to_string(&self, locale: &str) -> String {
+        let Some(formatter) = &*utils::get_datetime_formatter(locale, &self.format, self) else {
+            return self.to_string();
+        };
+        formatter
+            .format_to_string(&self.datetime.to_any())
+            .unwrap_or_else(|err| {
+                bevy::log::error!("Failed to format date time: {:?}", err);
+                self.to_string()
+            })
+    }
+}
+
+impl Default for DateTimeValue {
+    fn default() -> Self {
+        Self::new(
+            DateTime::from_minutes_since_local_unix_epoch(0),
+            DateTimeFormat::default(),
+        )
+    }
+}
+
+/// Formats as ISO 8601, `2020-01-21T08:25:07`
+impl std::fmt::Display for DateTimeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let date = &self.datetime.date;
+        let time = &self.datetime.time;
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            date.year().number,
+            date.month().ordinal,
+            date.day_of_month().0,
+            time.hour.number(),
+            time.minute.number(),
+            time.second.number()
+        )
+    }
+}
+
+/// Returns the date and time of a Unix timestamp, in UTC
+///
+/// Timestamps whose minutes don't fit in an `i32`, more than 4000 years away, are clamped
+fn datetime_from_unix_seconds(seconds: i64) -> DateTime<Iso> {
+    let range = i64::from(i32::MIN) * 60..=i64::from(i32::MAX) * 60 + 59;
+    let clamped = seconds.clamp(*range.start(), *range.end());
+    if clamped != seconds {
+        bevy::log::warn!(
+            "Unix timestamp out of range: {}, clamped to {}",
+            seconds,
+            clamped
+        );
+    }
+    let seconds = clamped;
+    let minutes = seconds.div_euclid(60) as i32;
+    let mut datetime = DateTime::from_minutes_since_local_unix_epoch(minutes);
+    datetime.time.second = (seconds.rem_euclid(60) as u8)
+        .try_into()
+        .expect("Seconds are always in range");
+    datetime
+}
+
+/// Component for spawning localized date and time entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the date and time formatted
+/// with `icu_datetime`, in the calendar of the locale
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, medium date and short time
+/// world.spawn(I18nDateTime::from_unix_seconds(1_579_595_107));
+///
+/// // With a chosen length
+/// world.spawn(
+///     I18nDateTime::from_unix_seconds(1_579_595_107)
+///         .with_format(DateTimeFormat::Date(DateTimeLength::Long)),
+/// );
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nDateTime::from_unix_seconds(1_579_595_107).with_locale("ja"));
+/// ```
+#[derive(Default, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nDateTime {
+    #[reflect(ignore)]
+    pub(crate) value: DateTimeValue,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nDateTime {
+    type Target = Text;
+
+    fn locale(&self, i18n: &I18n) -> String {
+        self.locale
+            .clone()
+            .unwrap_or_else(|| i18n.current().to_string())
+    }
+
+    fn has_fixed_locale(&self) -> bool {
+        self.locale.is_some()
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.value
+            .format_to_string(&i18n.resolve_locale(&self.locale(i18n)))
+    }
+}
+
+impl I18nDateTime {
+    /// Creates a new `I18nDateTime` component with the provided ISO date and time
+    pub fn new(datetime: DateTime<Iso>) -> Self {
+        Self {
+            value: DateTimeValue::new(datetime, DateTimeFormat::default()),
+            locale: None,
+        }
+    }
+
+    /// Creates a new `I18nDateTime` component from a Unix timestamp, in UTC
+    ///
+    /// Timestamps more than 4000 years away from 1970 are clamped
+    pub fn from_unix_seconds(seconds: i64) -> Self {
+        Self::new(datetime_from_unix_seconds(seconds))
+    }
+
+    /// Set the format of the date and time, see [DateTimeFormat]
+    pub fn with_format(mut self, format: DateTimeFormat) -> Self {
+        self.value.format = format;
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+}
+
+impl Component for I18nDateTime {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {
+        _hooks.on_add(|mut world, entity, _| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            debug!("Adding i18n date time: {}", val.value);
+            let translated = translate_entity(&mut world, entity, &val);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(format: DateTimeFormat) -> DateTimeValue {
+        DateTimeValue::new(datetime_from_unix_seconds(1_579_595_107), format)
+    }
+
+    #[test]
+    fn full_and_long_times_are_displayed_as_medium() {
+        let medium = value(DateTimeFormat::Time(DateTimeLength::Medium)).format_to_string("en");
+        assert_eq!(medium, "8:25:07\u{202f}AM");
+        for length in [DateTimeLength::Full, DateTimeLength::Long] {
+            assert_eq!(
+                value(DateTimeFormat::Time(length)).format_to_string("en"),
+                medium
+            );
+        }
+        assert_eq!(
+            value(DateTimeFormat::DateTime(
+                DateTimeLength::Full,
+                DateTimeLength::Full
+            ))
+            .format_to_string("en"),
+            "Tuesday, January 21, 2020, 8:25:07\u{202f}AM"
+        );
+    }
+
+    #[test]
+    fn medium_and_short_lengths() {
+        let time = |length| value(DateTimeFormat::Time(length)).format_to_string("en");
+        assert_eq!(time(DateTimeLength::Medium), "8:25:07\u{202f}AM");
+        assert_eq!(time(DateTimeLength::Short), "8:25\u{202f}AM");
+        let date = |length| value(DateTimeFormat::Date(length)).format_to_string("en");
+        assert_eq!(date(DateTimeLength::Medium), "Jan 21, 2020");
+        assert_eq!(date(DateTimeLength::Short), "1/21/20");
+    }
+
+    #[test]
+    fn out_of_range_timestamps_are_clamped() {
+        let max = datetime_from_unix_seconds(i64::MAX);
+        assert_eq!(
+            max,
+            datetime_from_unix_seconds(i64::from(i32::MAX) * 60 + 59)
+        );
+        assert_eq!(max.time.second.number(), 59);
+        let min = datetime_from_unix_seconds(i64::MIN);
+        assert_eq!(min, datetime_from_unix_seconds(i64::from(i32::MIN) * 60));
+        assert!(min.date.year().number < 0);
+    }
+
+    #[test]
+    fn unsupported_skeleton_falls_back_to_iso() {
+        let mut bag = components::Bag::default();
+        bag.hour = Some(components::Numeric::Numeric);
+        bag.time_zone_name = Some(components::TimeZoneName::LongGeneric);
+        assert_eq!(
+            value(DateTimeFormat::Skeleton(bag)).format_to_string("en"),
+            "2020-01-21T08:25:07"
+        );
+    }
+}
@@ -8,19 +8,21 @@ use bevy::{
     text::TextFont,
 };
 
-use crate::{
-    components::{I18nNumber, I18nText, I18nText2d},
-    prelude::I18nComponent,
-    resources::*,
-};
+use crate::resources::*;
 
 /// Component for spawning dynamic font entities that are managed by `bevy_simple_i18n`
 ///
 /// The font for the text entity will be automatically updated based on the locale set by the [I18n] resource
 ///
+/// Entities with an i18n component, including the custom ones registered with
+/// [crate::plugin::I18nComponentRegistration::register_i18n_component], use the font of the locale of the component
+///
 /// # Example
 ///
-/// ```
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
 /// world.spawn((I18nText::new("hello"), I18nFont::new("NotoSans")));
 /// ```
 #[derive(Default, Reflect, Debug, Clone)]
@@ -42,19 +44,17 @@ impl Component for I18nFont {
             let font_manager = world
                 .get_resource::<FontManager>()
                 .expect("Font manager has not been initialized");
+            let i18n = world
+                .get_resource::<I18n>()
+                .expect("I18n resource has not been initialized");
 
-            let locale = if let Some(i18n_text) = world.get::<I18nText>(entity) {
-                i18n_text.locale()
-            } else if let Some(i18n_number) = world.get::<I18nNumber>(entity) {
-                i18n_number.locale()
-            } else if let Some(i18n_text_2d) = world.get::<I18nText2d>(entity) {
-                i18n_text_2d.locale()
-            } else {
-                rust_i18n::locale().to_string()
-            };
+            let locale = world
+                .get_resource::<I18nComponents>()
+                .and_then(|components| components.locale(&world, entity, i18n))
+                .unwrap_or_else(|| i18n.current().to_string());
 
             let val = world.get::<Self>(entity).unwrap().clone();
-            let font_handler = font_manager.get(&val.0, locale);
+            let font_handler = font_manager.get(&val.0, &i18n.fallback_chain(&locale));
 
             debug!("Adding dynamic font: {}", val.0);
             if let Some(mut font) = world.get_mut::<TextFont>(entity) {
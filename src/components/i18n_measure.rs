@@ -0,0 +1,447 @@
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHooks, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    ui::widget::Text,
+};
+use icu_experimental::units::converter_factory::ConverterFactory;
+use icu_locid_transform::LocaleExpander;
+
+use crate::resources::I18n;
+
+use super::{
+    utils::{self, translate_entity},
+    I18nComponent, InterpolationType, NumberFormat, NumberValue,
+};
+
+/// Regions using the US customary units, every other region uses the metric system
+const US_REGIONS: &[&str] = &["US", "LR", "MM"];
+
+/// System of units preferred by a locale
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum MeasurementSystem {
+    /// Meters, kilograms, liters and degrees Celsius
+    #[default]
+    Metric,
+    /// Miles, pounds, gallons and degrees Fahrenheit
+    Us,
+}
+
+impl MeasurementSystem {
+    /// Returns the measurement system of the region of the locale, `en` is `en-US` and uses [MeasurementSystem::Us]
+    pub fn for_locale(locale: &str) -> Self {
+        let mut locale = utils::resolve_locale(locale, "measurement system");
+        utils::cached(String::new(), LocaleExpander::new).maximize(&mut locale.id);
+        match locale.id.region {
+            Some(region) if US_REGIONS.contains(&region.as_str()) => MeasurementSystem::Us,
+            _ => MeasurementSystem::Metric,
+        }
+    }
+}
+
+/// Unit of an [I18nMeasure]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[non_exhaustive]
+pub enum MeasureUnit {
+    Millimeter,
+    Centimeter,
+    Meter,
+    Kilometer,
+    Inch,
+    Foot,
+    Yard,
+    Mile,
+    MeterPerSecond,
+    KilometerPerHour,
+    MilePerHour,
+    Gram,
+    Kilogram,
+    Ounce,
+    Pound,
+    Milliliter,
+    Liter,
+    FluidOunce,
+    Gallon,
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl MeasureUnit {
+    /// Returns the CLDR identifier of the unit, `kilometer-per-hour`
+    ///
+    /// It's also the suffix of the translation key that overrides how the unit is displayed, `units.kilometer-per-hour`
+    pub fn identifier(&self) -> &'static str {
+        match self {
+            MeasureUnit::Millimeter => "millimeter",
+            MeasureUnit::Centimeter => "centimeter",
+            MeasureUnit::Meter => "meter",
+            MeasureUnit::Kilometer => "kilometer",
+            MeasureUnit::Inch => "inch",
+            MeasureUnit::Foot => "foot",
+            MeasureUnit::Yard => "yard",
+            MeasureUnit::Mile => "mile",
+            MeasureUnit::MeterPerSecond => "meter-per-second",
+            MeasureUnit::KilometerPerHour => "kilometer-per-hour",
+            MeasureUnit::MilePerHour => "mile-per-hour",
+            MeasureUnit::Gram => "gram",
+            MeasureUnit::Kilogram => "kilogram",
+            MeasureUnit::Ounce => "ounce",
+            MeasureUnit::Pound => "pound",
+            MeasureUnit::Milliliter => "milliliter",
+            MeasureUnit::Liter => "liter",
+            MeasureUnit::FluidOunce => "fluid-ounce",
+            MeasureUnit::Gallon => "gallon",
+            MeasureUnit::Celsius => "celsius",
+            MeasureUnit::Fahrenheit => "fahrenheit",
+            MeasureUnit::Kelvin => "kelvin",
+            MeasureUnit::Second => "second",
+            MeasureUnit::Minute => "minute",
+            MeasureUnit::Hour => "hour",
+            MeasureUnit::Day => "day",
+        }
+    }
+
+    /// Returns the short English symbol of the unit, used when the locale files don't override it
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            MeasureUnit::Millimeter => "mm",
+            MeasureUnit::Centimeter => "cm",
+            MeasureUnit::Meter => "m",
+            MeasureUnit::Kilometer => "km",
+            MeasureUnit::Inch => "in",
+            MeasureUnit::Foot => "ft",
+            MeasureUnit::Yard => "yd",
+            MeasureUnit::Mile => "mi",
+            MeasureUnit::MeterPerSecond => "m/s",
+            MeasureUnit::KilometerPerHour => "km/h",
+            MeasureUnit::MilePerHour => "mph",
+            MeasureUnit::Gram => "g",
+            MeasureUnit::Kilogram => "kg",
+            MeasureUnit::Ounce => "oz",
+            MeasureUnit::Pound => "lb",
+            MeasureUnit::Milliliter => "mL",
+            MeasureUnit::Liter => "L",
+            MeasureUnit::FluidOunce => "fl oz",
+            MeasureUnit::Gallon => "gal",
+            MeasureUnit::Celsius => "°C",
+            MeasureUnit::Fahrenheit => "°F",
+            MeasureUnit::Kelvin => "K",
+            MeasureUnit::Second => "s",
+            MeasureUnit::Minute => "min",
+            MeasureUnit::Hour => "h",
+            MeasureUnit::Day => "d",
+        }
+    }
+
+    /// Returns the unit of the measurement system that replaces this unit, if it isn't already part of it
+    ///
+    /// Units used by both systems, like durations, are never replaced
+    pub fn counterpart(&self, system: MeasurementSystem) -> Option<MeasureUnit> {
+        match system {
+            MeasurementSystem::Metric => match self {
+                MeasureUnit::Inch => Some(MeasureUnit::Centimeter),
+                MeasureUnit::Foot | MeasureUnit::Yard => Some(MeasureUnit::Meter),
+                MeasureUnit::Mile => Some(MeasureUnit::Kilometer),
+                MeasureUnit::MilePerHour => Some(MeasureUnit::KilometerPerHour),
+                MeasureUnit::Ounce => Some(MeasureUnit::Gram),
+                MeasureUnit::Pound => Some(MeasureUnit::Kilogram),
+                MeasureUnit::FluidOunce => Some(MeasureUnit::Milliliter),
+                MeasureUnit::Gallon => Some(MeasureUnit::Liter),
+                MeasureUnit::Fahrenheit => Some(MeasureUnit::Celsius),
+                _ => None,
+            },
+            MeasurementSystem::Us => match self {
+                MeasureUnit::Millimeter | MeasureUnit::Centimeter => Some(MeasureUnit::Inch),
+                MeasureUnit::Meter => Some(MeasureUnit::Foot),
+                MeasureUnit::Kilometer => Some(MeasureUnit::Mile),
+                MeasureUnit::KilometerPerHour => Some(MeasureUnit::MilePerHour),
+                MeasureUnit::Gram => Some(MeasureUnit::Ounce),
+                MeasureUnit::Kilogram => Some(MeasureUnit::Pound),
+                MeasureUnit::Milliliter => Some(MeasureUnit::FluidOunce),
+                MeasureUnit::Liter => Some(MeasureUnit::Gallon),
+                MeasureUnit::Celsius => Some(MeasureUnit::Fahrenheit),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Converts a value between two units with the CLDR conversion data, `None` if they aren't convertible
+fn convert(value: f64, from: MeasureUnit, to: MeasureUnit) -> Option<f64> {
+    let factory = utils::cached(String::new(), ConverterFactory::new);
+    let parser = factory.parser();
+    let from = parser.try_from_bytes(from.identifier().as_bytes()).ok()?;
+    let to = parser.try_from_bytes(to.identifier().as_bytes()).ok()?;
+    Some(factory.converter::<f64>(&from, &to)?.convert(&value))
+}
+
+/// Component for spawning localized measurement entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the value formatted for the locale,
+/// followed by the symbol of the unit. With [I18nMeasure::with_conversion], the value is converted to the
+/// [MeasurementSystem] of the locale, e.g. kilometers to miles for `en-US`
+///
+/// The symbols are the English ones, `km` or `mph`, in every locale since ICU4X doesn't format units yet.
+/// They can only be translated per locale with a `units.{identifier}` translation key, where `%{count}` is
+/// the formatted value, e.g. `units.mile: "%{count} miles"`. Plural forms are supported,
+/// `units.mile.one: "%{count} mile"`
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `12.5 km`
+/// world.spawn(I18nMeasure::new(12.5, MeasureUnit::Kilometer));
+///
+/// // Converted to the measurement system of the locale, `7.8 mi` in `en-US`, `12,5 km` in `fr`
+/// world.spawn(I18nMeasure::new(12.5, MeasureUnit::Kilometer).with_conversion());
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nMeasure::new(21, MeasureUnit::Celsius).with_conversion().with_locale("en-US"));
+/// ```
+#[derive(Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nMeasure {
+    pub(crate) value: f64,
+    pub(crate) unit: MeasureUnit,
+    /// Converts the value to the measurement system of the locale
+    pub(crate) convert: bool,
+    /// Rounding, padding, grouping and sign options, 1 fraction digit at most by default
+    pub(crate) format: NumberFormat,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl Default for I18nMeasure {
+    fn default() -> Self {
+        Self::new(0., MeasureUnit::Meter)
+    }
+}
+
+impl I18nComponent for I18nMeasure {
+    type Target = Text;
+
+    fn locale(&self, i18n: &I18n) -> String {
+        self.locale
+            .clone()
+            .unwrap_or_else(|| i18n.current().to_string())
+    }
+
+    fn has_fixed_locale(&self) -> bool {
+        self.locale.is_some()
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        let locale = i18n.resolve_locale(&self.locale(i18n));
+        let (value, unit) = self.converted(&locale);
+        let number = NumberValue::new(&utils::f64_to_fd(value), self.format);
+
+        let key = format!("units.{}", unit.identifier());
+        let args = [(
+            String::from("count"),
+            InterpolationType::Number(number.clone()),
+        )];
+        if let Some((pattern, found)) = utils::lookup_optional(i18n, &locale, &key, &args) {
+            return utils::interpolate(&pattern, &found, &args);
+        }
+        let number = number.format_to_string(&locale);
+        match unit {
+            MeasureUnit::Celsius | MeasureUnit::Fahrenheit => {
+                format!("{}{}", number, unit.symbol())
+            }
+            _ => format!("{} {}", number, unit.symbol()),
+        }
+    }
+}
+
+impl I18nMeasure {
+    /// Creates a new `I18nMeasure` component with the provided value and unit
+    pub fn new(value: impl Into<f64>, unit: MeasureUnit) -> Self {
+        Self {
+            value: value.into(),
+            unit,
+            convert: false,
+            format: NumberFormat::new().with_max_fraction_digits(1),
+            locale: None,
+        }
+    }
+
+    /// Convert the value to the [MeasurementSystem] of the locale, `km` to `mi` for `en-US` and back for `fr`
+    pub fn with_conversion(mut self) -> Self {
+        self.convert = true;
+        self
+    }
+
+    /// Set how the value is rounded and displayed, see [NumberFormat]
+    pub fn with_format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Returns the value and unit to display for the locale
+    fn converted(&self, locale: &str) -> (f64, MeasureUnit) {
+        if !self.convert {
+            return (self.value, self.unit);
+        }
+        let Some(unit) = self.unit.counterpart(MeasurementSystem::for_locale(locale)) else {
+            return (self.value, self.unit);
+        };
+        match convert(self.value, self.unit, unit) {
+            Some(value) => (value, unit),
+            None => {
+                bevy::log::error!(
+                    "Failed to convert {} from {} to {}",
+                    self.value,
+                    self.unit.identifier(),
+                    unit.identifier()
+                );
+                (self.value, self.unit)
+            }
+        }
+    }
+}
+
+impl Component for I18nMeasure {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {
+        _hooks.on_add(|mut world, entity, _| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            debug!(
+                "Adding i18n measure: {} {}",
+                val.value,
+                val.unit.identifier()
+            );
+            let translated = translate_entity(&mut world, entity, &val);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assets::LocaleFile, backend::LocaleFileBackend};
+
+    fn i18n(locale: &str, json: &str) -> I18n {
+        let mut backend = LocaleFileBackend::default();
+        backend.extend(&LocaleFile::parse(json, "json", locale).unwrap());
+        let mut i18n = I18n::new(backend);
+        i18n.set_locale(locale);
+        i18n
+    }
+
+    fn translate(measure: I18nMeasure, locale: &str) -> String {
+        measure.translate(&i18n(locale, "{}"))
+    }
+
+    #[test]
+    fn measurement_system_of_the_region() {
+        assert_eq!(MeasurementSystem::for_locale("en"), MeasurementSystem::Us);
+        assert_eq!(
+            MeasurementSystem::for_locale("en-US"),
+            MeasurementSystem::Us
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("en-LR"),
+            MeasurementSystem::Us
+        );
+        assert_eq!(MeasurementSystem::for_locale("my"), MeasurementSystem::Us);
+        assert_eq!(
+            MeasurementSystem::for_locale("en-GB"),
+            MeasurementSystem::Metric
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("fr"),
+            MeasurementSystem::Metric
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("ja"),
+            MeasurementSystem::Metric
+        );
+    }
+
+    #[test]
+    fn values_are_converted_to_the_system_of_the_locale() {
+        let speed = I18nMeasure::new(100, MeasureUnit::KilometerPerHour).with_conversion();
+        assert_eq!(translate(speed.clone(), "en"), "62.1 mph");
+        assert_eq!(translate(speed, "fr"), "100 km/h");
+
+        let temperature = I18nMeasure::new(21, MeasureUnit::Celsius).with_conversion();
+        assert_eq!(translate(temperature.clone(), "en"), "69.8°F");
+        assert_eq!(translate(temperature, "fr"), "21°C");
+
+        let distance = I18nMeasure::new(10, MeasureUnit::Mile).with_conversion();
+        assert_eq!(translate(distance, "de"), "16,1 km");
+
+        // Durations are the same in both systems, and values aren't converted without `with_conversion`
+        assert_eq!(
+            translate(
+                I18nMeasure::new(90, MeasureUnit::Minute).with_conversion(),
+                "en"
+            ),
+            "90 min"
+        );
+        assert_eq!(
+            translate(I18nMeasure::new(100, MeasureUnit::KilometerPerHour), "en"),
+            "100 km/h"
+        );
+    }
+
+    #[test]
+    fn values_are_rounded_to_1_fraction_digit_by_default() {
+        assert_eq!(
+            translate(I18nMeasure::new(1.26, MeasureUnit::Kilometer), "en"),
+            "1.3 km"
+        );
+        assert_eq!(
+            translate(I18nMeasure::new(1234.5, MeasureUnit::Meter), "de"),
+            "1.234,5 m"
+        );
+        let precise = I18nMeasure::new(1.256, MeasureUnit::Kilometer)
+            .with_format(NumberFormat::new().with_fraction_digits(2));
+        assert_eq!(translate(precise, "en"), "1.26 km");
+    }
+
+    #[test]
+    fn units_can_be_overridden_with_plural_keys() {
+        let i18n = i18n(
+            "en",
+            r#"{"units": {"mile": {"one": "%{count} mile", "other": "%{count} miles"}}}"#,
+        );
+        let miles = |value: f64| I18nMeasure::new(value, MeasureUnit::Mile).translate(&i18n);
+        assert_eq!(miles(1.), "1 mile");
+        assert_eq!(miles(2.5), "2.5 miles");
+        assert_eq!(
+            I18nMeasure::new(3, MeasureUnit::Kilometer).translate(&i18n),
+            "3 km"
+        );
+    }
+}
@@ -9,7 +9,12 @@ use bevy::{
 };
 use fixed_decimal::FixedDecimal;
 
-use super::{utils, I18nComponent};
+use crate::resources::I18n;
+
+use super::{
+    utils::{self, translate_entity},
+    Grouping, I18nComponent, Notation, NumberFormat, NumberValue, RoundingMode, SignDisplay,
+};
 
 /// Component for spawning translatable number entities that are managed by `bevy_simple_i18n`
 ///
@@ -19,10 +24,20 @@ use super::{utils, I18nComponent};
 ///
 /// # Example
 ///
-/// ```
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
 /// // Basic usage
 /// world.spawn(I18nNumber::new(200.40));
 ///
+/// // With format options, `+1,234.50` in `en`
+/// world.spawn(
+///     I18nNumber::new(1234.5)
+///         .with_fraction_digits(2)
+///         .with_sign_display(SignDisplay::Always),
+/// );
+///
 /// // With forced locale
 /// // overrides the global
 /// // does not update when the locale is changed
@@ -33,20 +48,28 @@ use super::{utils, I18nComponent};
 pub struct I18nNumber {
     #[reflect(ignore)]
     pub(crate) fixed_decimal: FixedDecimal,
+    /// Rounding, padding, grouping and sign options
+    pub(crate) format: NumberFormat,
     /// Locale for this specific translation, `None` to use the global locale
     pub(crate) locale: Option<String>,
 }
 
 impl I18nComponent for I18nNumber {
-    fn locale(&self) -> String {
+    type Target = Text;
+
+    fn locale(&self, i18n: &I18n) -> String {
         self.locale
             .clone()
-            .unwrap_or(rust_i18n::locale().to_string())
+            .unwrap_or_else(|| i18n.current().to_string())
     }
 
-    fn translate(&self) -> String {
-        utils::get_formatter(&self.locale(), &self.fixed_decimal)
-            .format_to_string(&self.fixed_decimal)
+    fn has_fixed_locale(&self) -> bool {
+        self.locale.is_some()
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        NumberValue::new(&self.fixed_decimal, self.format)
+            .format_to_string(&i18n.resolve_locale(&self.locale(i18n)))
     }
 }
 
@@ -55,10 +78,65 @@ impl I18nNumber {
     pub fn new(number: impl Into<f64>) -> Self {
         Self {
             fixed_decimal: utils::f64_to_fd(number.into()),
+            format: NumberFormat::default(),
             locale: None,
         }
     }
 
+    /// Set every format option at once, see [NumberFormat]
+    pub fn with_format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set how the number is written, [Notation::Standard] by default
+    pub fn with_notation(mut self, notation: Notation) -> Self {
+        self.format = self.format.with_notation(notation);
+        self
+    }
+
+    /// Display exactly this number of fraction digits, rounding or padding with zeros as needed
+    pub fn with_fraction_digits(mut self, digits: u8) -> Self {
+        self.format = self.format.with_fraction_digits(digits);
+        self
+    }
+
+    /// Pad the fraction with zeros up to this number of digits
+    pub fn with_min_fraction_digits(mut self, digits: u8) -> Self {
+        self.format = self.format.with_min_fraction_digits(digits);
+        self
+    }
+
+    /// Round the number to this number of fraction digits, see [I18nNumber::with_rounding]
+    pub fn with_max_fraction_digits(mut self, digits: u8) -> Self {
+        self.format = self.format.with_max_fraction_digits(digits);
+        self
+    }
+
+    /// Pad the integer with zeros up to this number of digits, `7` -> `007`
+    pub fn with_min_integer_digits(mut self, digits: u8) -> Self {
+        self.format = self.format.with_min_integer_digits(digits);
+        self
+    }
+
+    /// Set how the number is rounded to its maximum fraction digits, [RoundingMode::HalfEven] by default
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.format = self.format.with_rounding(rounding);
+        self
+    }
+
+    /// Set when grouping separators are displayed, [Grouping::Auto] by default
+    pub fn with_grouping(mut self, grouping: Grouping) -> Self {
+        self.format = self.format.with_grouping(grouping);
+        self
+    }
+
+    /// Set when the sign is displayed, [SignDisplay::Auto] by default
+    pub fn with_sign_display(mut self, sign_display: SignDisplay) -> Self {
+        self.format = self.format.with_sign_display(sign_display);
+        self
+    }
+
     /// Set the locale for this specific translation
     pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
         self.locale = Some(locale.into());
@@ -73,13 +151,14 @@ impl Component for I18nNumber {
         _hooks.on_add(|mut world, entity, _| {
             let val = world.get::<Self>(entity).unwrap().clone();
             debug!("Adding i18n number: {}", val.fixed_decimal);
+            let translated = translate_entity(&mut world, entity, &val);
             if let Some(mut text) = world.get_mut::<Text>(entity) {
-                **text = val.translate();
+                **text = translated;
             } else {
                 world
                     .commands()
                     .entity(entity)
-                    .insert(Text::new(val.translate()));
+                    .insert(Text::new(translated));
             }
         });
     }
@@ -0,0 +1,418 @@
+use std::hash::{Hash, Hasher};
+
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHooks, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    ui::widget::Text,
+    utils::{Duration, SystemTime},
+};
+use fixed_decimal::{FixedDecimal, Sign};
+
+use crate::resources::I18n;
+
+use super::{
+    utils::{self, translate_entity},
+    Grouping, I18nComponent, NumberFormat, NumberValue,
+};
+
+/// Marker of the entities whose i18n component is displayed as a relative time, or has relative time
+/// arguments, the only ones whose translation is updated as time elapses
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub(crate) struct HasRelativeTime;
+
+/// Width of the unit names of a [RelativeTimeFormat]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum RelativeTimeStyle {
+    /// `in 3 minutes`
+    #[default]
+    Long,
+    /// `in 3 min.`
+    Short,
+    /// `in 3m`, the same as [RelativeTimeStyle::Short] in many locales
+    Narrow,
+}
+
+/// How the time between now and a [RelativeTimeValue] is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum RelativeTimeFormat {
+    /// `in 3 minutes` or `2 days ago`, in the largest unit that fits
+    Numeric(RelativeTimeStyle),
+    /// Like [RelativeTimeFormat::Numeric], but with the words of the locale when it has one, `now`,
+    /// `tomorrow` or `last week`
+    Auto(RelativeTimeStyle),
+    /// The time left or elapsed as a clock, `1:05:30` or `4:07`
+    Clock,
+}
+
+impl Default for RelativeTimeFormat {
+    fn default() -> Self {
+        RelativeTimeFormat::Numeric(RelativeTimeStyle::Long)
+    }
+}
+
+/// Unit a relative time is displayed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeUnit {
+    /// Length of the unit in seconds, months and years are averaged over the 400 years of the Gregorian cycle
+    fn seconds(self) -> i64 {
+        match self {
+            TimeUnit::Second => 1,
+            TimeUnit::Minute => 60,
+            TimeUnit::Hour => 3_600,
+            TimeUnit::Day => 86_400,
+            TimeUnit::Week => 604_800,
+            TimeUnit::Month => 2_629_746,
+            TimeUnit::Year => 31_556_952,
+        }
+    }
+
+    /// Returns the largest unit that fits in the number of seconds
+    fn for_seconds(seconds: i64) -> Self {
+        [
+            TimeUnit::Year,
+            TimeUnit::Month,
+            TimeUnit::Week,
+            TimeUnit::Day,
+            TimeUnit::Hour,
+            TimeUnit::Minute,
+        ]
+        .into_iter()
+        .find(|unit| seconds.abs() >= unit.seconds())
+        .unwrap_or(TimeUnit::Second)
+    }
+}
+
+/// Point in time to display relative to now, along with its [RelativeTimeFormat]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeTimeValue {
+    pub(crate) target: SystemTime,
+    pub(crate) format: RelativeTimeFormat,
+}
+
+impl RelativeTimeValue {
+    pub(crate) fn new(target: SystemTime, format: RelativeTimeFormat) -> Self {
+        Self { target, format }
+    }
+
+    /// Returns the whole number of seconds from now to the target, negative in the past
+    ///
+    /// Future times are rounded up and past times down, so a countdown reaches `0` when the target is reached
+    fn seconds_from(&self, now: SystemTime) -> i64 {
+        match self.target.duration_since(now) {
+            Ok(left) => left.as_secs() as i64 + i64::from(left.subsec_nanos() > 0),
+            Err(err) => -(err.duration().as_secs() as i64),
+        }
+    }
+
+    /// Returns the unit and the whole number of units from now to the target, and whether it's in the past
+    fn relative(&self, now: SystemTime) -> (TimeUnit, i64, bool) {
+        let seconds = self.seconds_from(now);
+        let unit = TimeUnit::for_seconds(seconds);
+        (unit, seconds / unit.seconds(), self.target < now)
+    }
+
+    /// Returns a hash of what's displayed, it changes whenever the formatted text would change
+    pub(crate) fn time_state(&self, now: SystemTime, state: &mut impl Hasher) {
+        match self.format {
+            RelativeTimeFormat::Clock => self.seconds_from(now).hash(state),
+            _ => self.relative(now).hash(state),
+        }
+    }
+
+    /// Formats the time between now and the target for the provided locale
+    pub(crate) fn format_to_string(&self, locale: &str) -> String {
+        self.format_at(locale, SystemTime::now())
+    }
+
+    /// Formats the time between `now` and the target for the provided locale
+    fn format_at(&self, locale: &str, now: SystemTime) -> String {
+        let style = match self.format {
+            RelativeTimeFormat::Numeric(style) | RelativeTimeFormat::Auto(style) => style,
+            RelativeTimeFormat::Clock => return self.format_clock(locale, now),
+        };
+        let (unit, value, past) = self.relative(now);
+        let mut value = FixedDecimal::from(value.abs());
+        if past {
+            value.set_sign(Sign::Negative);
+        }
+        let auto = matches!(self.format, RelativeTimeFormat::Auto(_));
+        utils::get_relative_time_formatter(locale, self, style, unit, auto)
+            .format(value)
+            .to_string()
+    }
+
+    /// Formats the time left or elapsed as hours, minutes and seconds, with the digits of the locale
+    fn format_clock(&self, locale: &str, now: SystemTime) -> String {
+        let seconds = self.seconds_from(now).abs();
+        let (hours, minutes, seconds) = (seconds / 3_600, seconds / 60 % 60, seconds % 60);
+        let format = NumberFormat::new().with_grouping(Grouping::Never);
+        let part = |value: i64, format: NumberFormat| {
+            NumberValue::new(&FixedDecimal::from(value), format).format_to_string(locale)
+        };
+        let two_digits = format.with_min_integer_digits(2);
+        if hours > 0 {
+            format!(
+                "{}:{}:{}",
+                part(hours, format),
+                part(minutes, two_digits),
+                part(seconds, two_digits)
+            )
+        } else {
+            format!("{}:{}", part(minutes, format), part(seconds, two_digits))
+        }
+    }
+}
+
+impl Default for RelativeTimeValue {
+    fn default() -> Self {
+        Self::new(SystemTime::UNIX_EPOCH, RelativeTimeFormat::default())
+    }
+}
+
+/// Formats as the number of seconds from now, negative in the past, `-240`
+impl std::fmt::Display for RelativeTimeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.seconds_from(SystemTime::now()).fmt(f)
+    }
+}
+
+/// Returns the time at the provided number of seconds from now, negative for the past
+pub(crate) fn time_from_now(seconds: f64) -> SystemTime {
+    let now = SystemTime::now();
+    let offset = Duration::from_secs_f64(seconds.abs());
+    if seconds < 0. {
+        now - offset
+    } else {
+        now + offset
+    }
+}
+
+/// Component for spawning localized relative time entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the time between now and the
+/// target, e.g. `in 5 seconds` or `2 hours ago`, formatted for the locale with the CLDR data
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource,
+/// and as time elapses whenever the displayed text changes
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `4 minutes ago`
+/// world.spawn(I18nRelativeTime::from_unix_seconds(1_735_689_600));
+///
+/// // Cooldown, `in 30 seconds` and then `in 29 seconds`
+/// world.spawn(I18nRelativeTime::from_now(30.));
+///
+/// // Countdown as a clock, `1:05:30`
+/// world.spawn(I18nRelativeTime::from_now(3930.).with_format(RelativeTimeFormat::Clock));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nRelativeTime::from_now(-86_400.).with_locale("ja"));
+/// ```
+#[derive(Default, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nRelativeTime {
+    #[reflect(ignore)]
+    pub(crate) value: RelativeTimeValue,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nRelativeTime {
+    type Target = Text;
+
+    fn locale(&self, i18n: &I18n) -> String {
+        self.locale
+            .clone()
+            .unwrap_or_else(|| i18n.current().to_string())
+    }
+
+    fn has_fixed_locale(&self) -> bool {
+        self.locale.is_some()
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.value
+            .format_to_string(&i18n.resolve_locale(&self.locale(i18n)))
+    }
+
+    fn time_state(&self, now: SystemTime) -> Option<u64> {
+        let mut state = std::hash::DefaultHasher::new();
+        self.value.time_state(now, &mut state);
+        Some(state.finish())
+    }
+}
+
+impl I18nRelativeTime {
+    /// Creates a new `I18nRelativeTime` component displaying the time between now and the provided time
+    pub fn new(target: SystemTime) -> Self {
+        Self {
+            value: RelativeTimeValue::new(target, RelativeTimeFormat::default()),
+            locale: None,
+        }
+    }
+
+    /// Creates a new `I18nRelativeTime` component from a Unix timestamp
+    pub fn from_unix_seconds(seconds: i64) -> Self {
+        let offset = Duration::from_secs(seconds.unsigned_abs());
+        Self::new(if seconds < 0 {
+            SystemTime::UNIX_EPOCH - offset
+        } else {
+            SystemTime::UNIX_EPOCH + offset
+        })
+    }
+
+    /// Creates a new `I18nRelativeTime` component for the provided number of seconds from now,
+    /// negative for the past
+    pub fn from_now(seconds: f64) -> Self {
+        Self::new(time_from_now(seconds))
+    }
+
+    /// Set how the time is displayed, see [RelativeTimeFormat]
+    pub fn with_format(mut self, format: RelativeTimeFormat) -> Self {
+        self.value.format = format;
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+}
+
+impl Component for I18nRelativeTime {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {
+        _hooks.on_add(|mut world, entity, _| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            debug!("Adding i18n relative time: {}", val.value);
+            let translated = translate_entity(&mut world, entity, &val);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::DefaultHasher;
+
+    use super::*;
+
+    fn value(now: SystemTime, seconds: f64, format: RelativeTimeFormat) -> RelativeTimeValue {
+        let offset = Duration::from_secs_f64(seconds.abs());
+        let target = if seconds < 0. {
+            now - offset
+        } else {
+            now + offset
+        };
+        RelativeTimeValue::new(target, format)
+    }
+
+    fn format(seconds: f64, format: RelativeTimeFormat, locale: &str) -> String {
+        let now = SystemTime::now();
+        value(now, seconds, format).format_at(locale, now)
+    }
+
+    fn state(value: &RelativeTimeValue, now: SystemTime) -> u64 {
+        let mut state = DefaultHasher::new();
+        value.time_state(now, &mut state);
+        state.finish()
+    }
+
+    #[test]
+    fn future_seconds_are_rounded_up_and_past_seconds_down() {
+        let now = SystemTime::now();
+        let seconds = |s| value(now, s, RelativeTimeFormat::Clock).seconds_from(now);
+        assert_eq!(seconds(1.5), 2);
+        assert_eq!(seconds(-1.5), -1);
+        assert_eq!(seconds(0.), 0);
+        assert_eq!(seconds(-240.), -240);
+    }
+
+    #[test]
+    fn time_state_changes_with_the_displayed_text() {
+        let now = SystemTime::now();
+        let numeric = value(now, -240., RelativeTimeFormat::default());
+        // Still `4 minutes ago` 10 seconds later, but `5 minutes ago` a minute later
+        assert_eq!(
+            state(&numeric, now),
+            state(&numeric, now + Duration::from_secs(10))
+        );
+        assert_ne!(
+            state(&numeric, now),
+            state(&numeric, now + Duration::from_secs(60))
+        );
+
+        let clock = value(now, 30., RelativeTimeFormat::Clock);
+        assert_ne!(
+            state(&clock, now),
+            state(&clock, now + Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn clocks_show_hours_minutes_and_seconds() {
+        let clock = RelativeTimeFormat::Clock;
+        assert_eq!(format(3930., clock, "en"), "1:05:30");
+        assert_eq!(format(-3930., clock, "en"), "1:05:30");
+        assert_eq!(format(245., clock, "en"), "4:05");
+        assert_eq!(format(7., clock, "en"), "0:07");
+        assert_eq!(format(0., clock, "en"), "0:00");
+    }
+
+    #[test]
+    fn the_largest_unit_that_fits_is_used() {
+        let long = RelativeTimeFormat::Numeric(RelativeTimeStyle::Long);
+        assert_eq!(format(-240., long, "en"), "4 minutes ago");
+        assert_eq!(format(30., long, "en"), "in 30 seconds");
+        assert_eq!(format(-86_400., long, "en"), "1 day ago");
+        assert_eq!(format(3. * 604_800., long, "en"), "in 3 weeks");
+        assert_eq!(format(-240., long, "fr"), "il y a 4 minutes");
+        assert_eq!(
+            format(
+                180.,
+                RelativeTimeFormat::Numeric(RelativeTimeStyle::Short),
+                "en"
+            ),
+            "in 3 min."
+        );
+    }
+
+    #[test]
+    fn auto_format_uses_the_words_of_the_locale() {
+        let auto = RelativeTimeFormat::Auto(RelativeTimeStyle::Long);
+        assert_eq!(format(-86_400., auto, "en"), "yesterday");
+        assert_eq!(format(86_400., auto, "en"), "tomorrow");
+        assert_eq!(format(-240., auto, "en"), "4 minutes ago");
+        assert_eq!(format(0., auto, "en"), "now");
+    }
+}
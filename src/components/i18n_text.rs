@@ -3,15 +3,11 @@ use bevy::{
         component::{Component, ComponentHooks, StorageType},
         reflect::ReflectComponent,
     },
-    log::debug,
     reflect::Reflect,
     ui::widget::Text,
 };
 
-#[cfg(feature = "numbers")]
-use fixed_decimal::FixedDecimal;
-
-use super::{utils::translate_by_key, I18nComponent};
+use super::text_translation::{impl_text_translation, translate_added, TextTranslation};
 
 /// Component for spawning translatable text entities that are managed by `bevy_simple_i18n`
 ///
@@ -29,7 +25,10 @@ use super::{utils::translate_by_key, I18nComponent};
 /// }
 /// ```
 ///
-/// ```
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
 /// // Basic usage
 /// world.spawn(I18nText::new("hello"));
 ///
@@ -44,86 +43,179 @@ use super::{utils::translate_by_key, I18nComponent};
 #[derive(Default, Reflect, Debug, Clone)]
 #[reflect(Component)]
 pub struct I18nText {
-    /// Translation key for i18n
-    key: String,
-    /// Interpolation arguments for the translation key
-    args: Vec<(String, InterpolationType)>,
-    /// Locale for this specific translation, `None` to use the global locale
-    pub(crate) locale: Option<String>,
+    /// Translation key, interpolation arguments and locale
+    translation: TextTranslation,
 }
 
-impl I18nComponent for I18nText {
-    fn locale(&self) -> String {
-        self.locale
-            .clone()
-            .unwrap_or(rust_i18n::locale().to_string())
-    }
-
-    fn translate(&self) -> String {
-        translate_by_key(&self.locale(), &self.key, &self.args)
-    }
-}
+impl_text_translation!(I18nText, Text);
 
 impl I18nText {
     /// Creates a new `I18nText` component with the provided translation key
     pub fn new(str: impl Into<String>) -> Self {
         Self {
-            key: str.into(),
-            args: vec![],
-            locale: None,
+            translation: TextTranslation::new(str),
         }
     }
 
-    /// Set the locale for this specific translation
-    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
-        self.locale = Some(locale.into());
+    /// Display the translation as rich text markup, e.g. `Deal <b>%{dmg}</b> <color=fire>fire</color> damage`
+    ///
+    /// The `Text` is rebuilt as `TextSpan` children styled with the [super::RichTextStyles] registered by
+    /// the app, whenever the translation changes. The string and list arguments are escaped, so they're
+    /// displayed as they are
+    pub fn with_rich_text(mut self) -> Self {
+        self.translation.rich_text = true;
         self
     }
+}
 
-    /// Add a standard string interpolation argument to the translation key
-    ///
-    /// This method can be called as many times as needed
-    pub fn with_arg(mut self, key: impl Into<String>, value: impl ToString) -> Self {
-        self.args
-            .push((key.into(), InterpolationType::String(value.to_string())));
-        self
+impl Component for I18nText {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(translate_added::<Self>);
     }
+}
 
+/// Value of an interpolation argument, passed to the [crate::backend::TranslationBackend]
+///
+/// Formats as the raw value, numbers are only localized when they are interpolated into the translation
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum InterpolationType {
+    String(String),
+    /// String that also selects the variant of the translation, see [I18nText::with_select_arg]
+    Select(String),
     #[cfg(feature = "numbers")]
-    /// Add a number interpolation argument to the translation key
-    ///
-    /// This method can be called as many times as needed
-    pub fn with_num_arg(mut self, key: impl Into<String>, value: impl Into<f64>) -> Self {
-        self.args.push((
-            key.into(),
-            InterpolationType::Number(super::utils::f64_to_fd(value.into())),
-        ));
-        self
+    Number(#[reflect(ignore)] super::NumberValue),
+    #[cfg(feature = "datetime")]
+    DateTime(#[reflect(ignore)] super::DateTimeValue),
+    #[cfg(feature = "currency")]
+    Currency(#[reflect(ignore)] super::CurrencyValue),
+    #[cfg(feature = "relative_time")]
+    RelativeTime(#[reflect(ignore)] super::RelativeTimeValue),
+    #[cfg(feature = "lists")]
+    List(#[reflect(ignore)] super::ListValue),
+    /// Key or button bound to an action, interpolated as its translated name, see [I18nText::with_input_arg]
+    Input(super::InputBinding),
+}
+
+impl From<String> for InterpolationType {
+    fn from(value: String) -> Self {
+        InterpolationType::String(value)
     }
 }
 
-impl Component for I18nText {
-    const STORAGE_TYPE: StorageType = StorageType::Table;
+impl From<&str> for InterpolationType {
+    fn from(value: &str) -> Self {
+        InterpolationType::String(value.to_string())
+    }
+}
+
+impl From<super::InputBinding> for InterpolationType {
+    fn from(value: super::InputBinding) -> Self {
+        InterpolationType::Input(value)
+    }
+}
 
-    fn register_component_hooks(_hooks: &mut ComponentHooks) {
-        _hooks.on_add(|mut world, entity, _| {
-            let val = world.get::<Self>(entity).unwrap().clone();
-            debug!("Adding i18n text: {}", val.key);
-            if let Some(mut text) = world.get_mut::<Text>(entity) {
-                **text = val.translate();
-            } else {
-                world
-                    .commands()
-                    .entity(entity)
-                    .insert(Text::new(val.translate()));
+/// Numbers are interpolated with the default [super::NumberFormat]
+#[cfg(feature = "numbers")]
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for InterpolationType {
+                fn from(value: $ty) -> Self {
+                    InterpolationType::Number(super::NumberValue::new(
+                        &fixed_decimal::FixedDecimal::from(value),
+                        Default::default(),
+                    ))
+                }
             }
-        });
+        )*
+    };
+}
+
+#[cfg(feature = "numbers")]
+impl_from_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(feature = "numbers")]
+impl InterpolationType {
+    /// Number displayed with the provided [super::NumberFormat]
+    ///
+    /// Infinity and NaN can't be formatted for a locale, they're interpolated as the strings `inf` and `NaN`
+    pub(crate) fn number(value: f64, format: super::NumberFormat) -> Self {
+        if !value.is_finite() {
+            return InterpolationType::String(value.to_string());
+        }
+        InterpolationType::Number(super::NumberValue::new(
+            &super::utils::f64_to_fd(value),
+            format,
+        ))
     }
 }
 
-#[derive(Reflect, Debug, Clone)]
-pub(crate) enum InterpolationType {
-    String(String),
-    #[cfg(feature = "numbers")]
-    Number(#[reflect(ignore)] FixedDecimal),
+#[cfg(feature = "numbers")]
+impl From<f64> for InterpolationType {
+    fn from(value: f64) -> Self {
+        InterpolationType::number(value, Default::default())
+    }
+}
+
+/// Converted from its shortest representation, so `0.1` isn't displayed as `0.10000000149011612`
+#[cfg(feature = "numbers")]
+impl From<f32> for InterpolationType {
+    fn from(value: f32) -> Self {
+        value.to_string().parse::<f64>().unwrap_or(f64::NAN).into()
+    }
+}
+
+impl std::fmt::Display for InterpolationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterpolationType::String(v) | InterpolationType::Select(v) => v.fmt(f),
+            #[cfg(feature = "numbers")]
+            InterpolationType::Number(v) => v.fmt(f),
+            #[cfg(feature = "datetime")]
+            InterpolationType::DateTime(v) => v.fmt(f),
+            #[cfg(feature = "currency")]
+            InterpolationType::Currency(v) => v.fmt(f),
+            #[cfg(feature = "relative_time")]
+            InterpolationType::RelativeTime(v) => v.fmt(f),
+            #[cfg(feature = "lists")]
+            InterpolationType::List(v) => v.fmt(f),
+            InterpolationType::Input(v) => v.fmt(f),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "numbers"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_numbers_are_interpolated_as_strings() {
+        assert_eq!(
+            InterpolationType::from(f32::NAN),
+            InterpolationType::String("NaN".to_string())
+        );
+        assert_eq!(
+            InterpolationType::from(f64::INFINITY),
+            InterpolationType::String("inf".to_string())
+        );
+        assert_eq!(
+            InterpolationType::from(f32::NEG_INFINITY),
+            InterpolationType::String("-inf".to_string())
+        );
+        assert!(matches!(
+            InterpolationType::from(0.1f32),
+            InterpolationType::Number(value) if value.to_string() == "0.1"
+        ));
+        let text = I18nText::new("score").with_num_arg("score", f64::NAN);
+        assert_eq!(
+            text.translation.args,
+            [(
+                "score".to_string(),
+                InterpolationType::String("NaN".to_string())
+            )]
+        );
+    }
 }
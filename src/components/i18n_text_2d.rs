@@ -3,12 +3,11 @@ use bevy::{
         component::{Component, ComponentHooks, StorageType},
         reflect::ReflectComponent,
     },
-    log::debug,
     reflect::Reflect,
     text::Text2d,
 };
 
-use super::{utils::translate_by_key, I18nComponent, InterpolationType};
+use super::text_translation::{impl_text_translation, translate_added, TextTranslation};
 
 /// Component for spawning translatable text 2d entities that are managed by `bevy_simple_i18n`
 ///
@@ -26,7 +25,10 @@ use super::{utils::translate_by_key, I18nComponent, InterpolationType};
 /// }
 /// ```
 ///
-/// ```
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
 /// // Basic usage
 /// world.spawn(I18nText2d::new("hello"));
 ///
@@ -41,79 +43,25 @@ use super::{utils::translate_by_key, I18nComponent, InterpolationType};
 #[derive(Default, Reflect, Debug, Clone)]
 #[reflect(Component)]
 pub struct I18nText2d {
-    /// Translation key for i18n
-    key: String,
-    /// Interpolation arguments for the translation key
-    args: Vec<(String, InterpolationType)>,
-    /// Locale for this specific translation, `None` to use the global locale
-    pub(crate) locale: Option<String>,
+    /// Translation key, interpolation arguments and locale
+    translation: TextTranslation,
 }
 
-impl I18nComponent for I18nText2d {
-    fn locale(&self) -> String {
-        self.locale
-            .clone()
-            .unwrap_or(rust_i18n::locale().to_string())
-    }
-
-    fn translate(&self) -> String {
-        translate_by_key(&self.locale(), &self.key, &self.args)
-    }
-}
+impl_text_translation!(I18nText2d, Text2d);
 
 impl I18nText2d {
     /// Creates a new [I18nText2d] component with the provided translation key
     pub fn new(str: impl Into<String>) -> Self {
         Self {
-            key: str.into(),
-            args: vec![],
-            locale: None,
+            translation: TextTranslation::new(str),
         }
     }
-
-    /// Set the locale for this specific translation
-    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
-        self.locale = Some(locale.into());
-        self
-    }
-
-    /// Add a standard string interpolation argument to the translation key
-    ///
-    /// This method can be called as many times as needed
-    pub fn with_arg(mut self, key: impl Into<String>, value: impl ToString) -> Self {
-        self.args
-            .push((key.into(), InterpolationType::String(value.to_string())));
-        self
-    }
-
-    #[cfg(feature = "numbers")]
-    /// Add a number interpolation argument to the translation key
-    ///
-    /// This method can be called as many times as needed
-    pub fn with_num_arg(mut self, key: impl Into<String>, value: impl Into<f64>) -> Self {
-        self.args.push((
-            key.into(),
-            InterpolationType::Number(super::utils::f64_to_fd(value.into())),
-        ));
-        self
-    }
 }
 
 impl Component for I18nText2d {
     const STORAGE_TYPE: StorageType = StorageType::Table;
 
-    fn register_component_hooks(_hooks: &mut ComponentHooks) {
-        _hooks.on_add(|mut world, entity, _| {
-            let val = world.get::<Self>(entity).unwrap().clone();
-            debug!("Adding i18n text 2d: {}", val.key);
-            if let Some(mut text) = world.get_mut::<Text2d>(entity) {
-                **text = val.translate();
-            } else {
-                world
-                    .commands()
-                    .entity(entity)
-                    .insert(Text2d::new(val.translate()));
-            }
-        });
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(translate_added::<Self>);
     }
 }
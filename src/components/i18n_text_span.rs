@@ -0,0 +1,74 @@
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHooks, StorageType},
+        reflect::ReflectComponent,
+    },
+    reflect::Reflect,
+    text::TextSpan,
+};
+
+use super::text_translation::{impl_text_translation, translate_added, TextTranslation};
+
+/// Component for spawning translatable text span entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy [TextSpan] component with the translated text using the provided key,
+/// so a single text block can mix translated and untranslated spans
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// # Example
+///
+/// ```json
+/// // en.json
+/// {
+///     "hello": "Hello, World!",
+///     "greet": "Hello, %{name}!"
+/// }
+/// ```
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `'hello' => Hello, World!`
+/// world
+///     .spawn(Text::new("'hello' => "))
+///     .with_child(I18nTextSpan::new("hello"));
+///
+/// // With interpolation arguments
+/// world
+///     .spawn(Text::new("Greeting: "))
+///     .with_child(I18nTextSpan::new("greet").with_arg("name", "Bevy User"));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world
+///     .spawn(Text::new("Always Japanese: "))
+///     .with_child(I18nTextSpan::new("hello").with_locale("ja"));
+/// ```
+#[derive(Default, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nTextSpan {
+    /// Translation key, interpolation arguments and locale
+    translation: TextTranslation,
+}
+
+impl_text_translation!(I18nTextSpan, TextSpan);
+
+impl I18nTextSpan {
+    /// Creates a new [I18nTextSpan] component with the provided translation key
+    pub fn new(str: impl Into<String>) -> Self {
+        Self {
+            translation: TextTranslation::new(str),
+        }
+    }
+}
+
+impl Component for I18nTextSpan {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(translate_added::<Self>);
+    }
+}
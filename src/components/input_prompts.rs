@@ -0,0 +1,284 @@
+use bevy::{
+    ecs::{
+        reflect::ReflectResource,
+        system::{Query, Res, ResMut, Resource},
+    },
+    input::{
+        gamepad::{Gamepad, GamepadButton},
+        keyboard::KeyCode,
+        mouse::MouseButton,
+        ButtonInput,
+    },
+    reflect::Reflect,
+    utils::HashMap,
+};
+
+use crate::resources::I18n;
+
+use super::utils;
+
+/// Kind of input device the player is using, it selects the [InputBinding] displayed for an action
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum InputDevice {
+    /// Keyboard and mouse
+    #[default]
+    KeyboardMouse,
+    /// Any gamepad
+    Gamepad,
+}
+
+/// Key or button bound to an action
+///
+/// Its name is translated with the `input.key.{KeyCode}`, `input.mouse.{MouseButton}` and
+/// `input.gamepad.{GamepadButton}` keys, e.g. `input.key.Space` or `input.gamepad.South`, and falls
+/// back to the name of the variant, `Space` or `A` for `KeyCode::KeyA`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum InputBinding {
+    /// Keyboard key
+    Key(KeyCode),
+    /// Mouse button
+    Mouse(MouseButton),
+    /// Gamepad button
+    Gamepad(GamepadButton),
+}
+
+impl InputBinding {
+    /// Returns the kind of device of the key or button
+    pub fn device(&self) -> InputDevice {
+        match self {
+            InputBinding::Key(_) | InputBinding::Mouse(_) => InputDevice::KeyboardMouse,
+            InputBinding::Gamepad(_) => InputDevice::Gamepad,
+        }
+    }
+
+    /// Returns the translation key of the name of the key or button
+    fn label_key(&self) -> String {
+        match self {
+            InputBinding::Key(key) => format!("input.key.{:?}", key),
+            InputBinding::Mouse(button) => format!("input.mouse.{:?}", button),
+            InputBinding::Gamepad(button) => format!("input.gamepad.{:?}", button),
+        }
+    }
+
+    /// Returns the name of the key or button translated for the locale, or the name of the variant
+    /// if there is no translation
+    pub(crate) fn format_to_string(&self, i18n: &I18n, locale: &str) -> String {
+        utils::lookup_optional(i18n, locale, &self.label_key(), &[])
+            .map_or_else(|| self.to_string(), |(label, _)| label)
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        InputBinding::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        InputBinding::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for InputBinding {
+    fn from(button: GamepadButton) -> Self {
+        InputBinding::Gamepad(button)
+    }
+}
+
+/// Formats as the name of the variant, `Space`, and as the letter or digit for `KeyA` and `Digit1`
+impl std::fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            InputBinding::Key(key) => format!("{:?}", key),
+            InputBinding::Mouse(button) => format!("{:?}", button),
+            InputBinding::Gamepad(button) => format!("{:?}", button),
+        };
+        let short = ["Key", "Digit"]
+            .iter()
+            .find_map(|prefix| name.strip_prefix(prefix))
+            .filter(|rest| rest.chars().count() == 1);
+        short.unwrap_or(&name).fmt(f)
+    }
+}
+
+/// Key and button bindings of the actions of the app, along with the [InputDevice] in use
+///
+/// The device switches automatically to the last one a key or button was pressed on, and the
+/// translations with input arguments are updated whenever it changes, see
+/// [crate::components::I18nText::with_input_arg]
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// fn setup(mut prompts: ResMut<InputPrompts>) {
+///     prompts
+///         .bind("jump", KeyCode::Space)
+///         .bind("jump", GamepadButton::South);
+/// }
+/// ```
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct InputPrompts {
+    device: InputDevice,
+    bindings: HashMap<String, Vec<InputBinding>>,
+}
+
+impl InputPrompts {
+    /// Binds a key or button to an action, replacing the binding of the action for the same device
+    pub fn bind(
+        &mut self,
+        action: impl Into<String>,
+        binding: impl Into<InputBinding>,
+    ) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self.bindings.entry(action.into()).or_default();
+        bindings.retain(|bound| bound.device() != binding.device());
+        bindings.push(binding);
+        self
+    }
+
+    /// Removes the bindings of an action
+    pub fn unbind(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    /// Returns the binding of an action for the device in use, or its first binding if it has none
+    /// for this device
+    pub fn binding(&self, action: &str) -> Option<InputBinding> {
+        let bindings = self.bindings.get(action)?;
+        bindings
+            .iter()
+            .find(|binding| binding.device() == self.device)
+            .or(bindings.first())
+            .copied()
+    }
+
+    /// Returns the device in use
+    pub fn device(&self) -> InputDevice {
+        self.device
+    }
+
+    /// Set the device in use, it is also set automatically whenever a key or button is pressed
+    pub fn set_device(&mut self, device: InputDevice) {
+        self.device = device;
+    }
+}
+
+/// Switches the [InputDevice] of the [InputPrompts] to the last one a key or button was pressed on
+pub(crate) fn update_input_device(
+    mut prompts: ResMut<InputPrompts>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mouse: Option<Res<ButtonInput<MouseButton>>>,
+    gamepads: Query<&Gamepad>,
+) {
+    let device = if gamepads
+        .iter()
+        .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+    {
+        InputDevice::Gamepad
+    } else if keys.is_some_and(|keys| keys.get_just_pressed().next().is_some())
+        || mouse.is_some_and(|mouse| mouse.get_just_pressed().next().is_some())
+    {
+        InputDevice::KeyboardMouse
+    } else {
+        return;
+    };
+    if prompts.device != device {
+        prompts.device = device;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::{assets::LocaleFile, backend::LocaleFileBackend};
+
+    fn prompts() -> InputPrompts {
+        let mut prompts = InputPrompts::default();
+        prompts
+            .bind("jump", KeyCode::Space)
+            .bind("jump", GamepadButton::South)
+            .bind("fire", MouseButton::Left);
+        prompts
+    }
+
+    #[test]
+    fn bindings_are_looked_up_for_the_device_in_use() {
+        let mut prompts = prompts();
+        assert_eq!(prompts.binding("jump"), Some(KeyCode::Space.into()));
+        assert_eq!(prompts.binding("fire"), Some(MouseButton::Left.into()));
+        assert_eq!(prompts.binding("crouch"), None);
+
+        prompts.set_device(InputDevice::Gamepad);
+        assert_eq!(prompts.binding("jump"), Some(GamepadButton::South.into()));
+        // Actions without a gamepad binding fall back to their first binding
+        assert_eq!(prompts.binding("fire"), Some(MouseButton::Left.into()));
+
+        // A binding replaces the one of the same device
+        prompts.bind("jump", GamepadButton::East);
+        assert_eq!(prompts.binding("jump"), Some(GamepadButton::East.into()));
+        prompts.set_device(InputDevice::KeyboardMouse);
+        assert_eq!(prompts.binding("jump"), Some(KeyCode::Space.into()));
+
+        prompts.unbind("jump");
+        assert_eq!(prompts.binding("jump"), None);
+    }
+
+    #[test]
+    fn names_fall_back_to_the_key_name() {
+        let mut backend = LocaleFileBackend::default();
+        backend.extend(
+            &LocaleFile::parse(r#"{"input": {"key": {"Space": "Espace"}}}"#, "json", "fr").unwrap(),
+        );
+        let i18n = I18n::new(backend);
+        let name = |binding: InputBinding| binding.format_to_string(&i18n, "fr");
+        assert_eq!(name(KeyCode::Space.into()), "Espace");
+        assert_eq!(name(KeyCode::KeyA.into()), "A");
+        assert_eq!(name(KeyCode::Digit1.into()), "1");
+        assert_eq!(name(KeyCode::Enter.into()), "Enter");
+        assert_eq!(name(MouseButton::Left.into()), "Left");
+        assert_eq!(name(GamepadButton::South.into()), "South");
+    }
+
+    #[test]
+    fn device_switches_to_the_last_one_pressed() {
+        let mut world = World::new();
+        world.insert_resource(prompts());
+        world.init_resource::<ButtonInput<KeyCode>>();
+        let gamepad = world.spawn(Gamepad::default()).id();
+        let device = |world: &mut World| {
+            world.run_system_once(update_input_device).unwrap();
+            world.resource::<InputPrompts>().device()
+        };
+        assert_eq!(device(&mut world), InputDevice::KeyboardMouse);
+
+        world
+            .get_mut::<Gamepad>(gamepad)
+            .unwrap()
+            .digital_mut()
+            .press(GamepadButton::South);
+        assert_eq!(device(&mut world), InputDevice::Gamepad);
+        assert_eq!(
+            world.resource::<InputPrompts>().binding("jump"),
+            Some(GamepadButton::South.into())
+        );
+
+        // Nothing newly pressed, the device doesn't change
+        world
+            .get_mut::<Gamepad>(gamepad)
+            .unwrap()
+            .digital_mut()
+            .clear();
+        assert_eq!(device(&mut world), InputDevice::Gamepad);
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        assert_eq!(device(&mut world), InputDevice::KeyboardMouse);
+    }
+}
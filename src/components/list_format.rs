@@ -0,0 +1,116 @@
+use bevy::reflect::Reflect;
+
+use super::utils;
+
+/// How the items of a list are joined
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ListStyle {
+    /// All of the items, `Alice, Bob, and Carol`
+    #[default]
+    And,
+    /// Any one of the items, `Alice, Bob, or Carol`
+    Or,
+    /// Parts of a single measure, `5 lb, 12 oz`
+    Unit,
+}
+
+/// Width of the separators and conjunctions of a list
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ListWidth {
+    /// `Alice, Bob, and Carol`
+    #[default]
+    Wide,
+    /// `Alice, Bob, & Carol`
+    Short,
+    /// `Alice, Bob, Carol`
+    Narrow,
+}
+
+/// Items to join with the list patterns of the locale, along with the [ListStyle] and [ListWidth]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ListValue {
+    pub(crate) items: Vec<String>,
+    pub(crate) style: ListStyle,
+    pub(crate) width: ListWidth,
+}
+
+impl ListValue {
+    pub(crate) fn new(items: Vec<String>, style: ListStyle, width: ListWidth) -> Self {
+        Self {
+            items,
+            style,
+            width,
+        }
+    }
+
+    /// Joins the items for the provided locale
+    pub(crate) fn format_to_string(&self, locale: &str) -> String {
+        utils::get_list_formatter(locale, self, self.style, self.width)
+            .format_to_string(self.items.iter())
+    }
+}
+
+/// Joins the items with commas, `Alice, Bob, Carol`
+impl std::fmt::Display for ListValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.items.join(", ").fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(items: &[&str], style: ListStyle, width: ListWidth, locale: &str) -> String {
+        let items = items.iter().map(|item| item.to_string()).collect();
+        ListValue::new(items, style, width).format_to_string(locale)
+    }
+
+    const NAMES: &[&str] = &["Alice", "Bob", "Carol"];
+
+    #[test]
+    fn items_are_joined_for_the_locale() {
+        let and = |locale| list(NAMES, ListStyle::And, ListWidth::Wide, locale);
+        assert_eq!(and("en"), "Alice, Bob, and Carol");
+        assert_eq!(and("de"), "Alice, Bob und Carol");
+        assert_eq!(and("ja"), "Alice、Bob、Carol");
+        assert_eq!(
+            list(&["Alice", "Bob"], ListStyle::And, ListWidth::Wide, "en"),
+            "Alice and Bob"
+        );
+        assert_eq!(
+            list(&["Alice"], ListStyle::And, ListWidth::Wide, "en"),
+            "Alice"
+        );
+    }
+
+    #[test]
+    fn styles_and_widths() {
+        let en = |style, width| list(NAMES, style, width, "en");
+        assert_eq!(en(ListStyle::And, ListWidth::Short), "Alice, Bob, & Carol");
+        assert_eq!(en(ListStyle::And, ListWidth::Narrow), "Alice, Bob, Carol");
+        assert_eq!(en(ListStyle::Or, ListWidth::Wide), "Alice, Bob, or Carol");
+        assert_eq!(en(ListStyle::Or, ListWidth::Narrow), "Alice, Bob, or Carol");
+        assert_eq!(
+            list(
+                &["Alice", "Bob", "Carol"],
+                ListStyle::Or,
+                ListWidth::Wide,
+                "de"
+            ),
+            "Alice, Bob oder Carol"
+        );
+
+        let weight = |width| list(&["5 lb", "12 oz"], ListStyle::Unit, width, "en");
+        assert_eq!(weight(ListWidth::Wide), "5 lb, 12 oz");
+        assert_eq!(weight(ListWidth::Short), "5 lb, 12 oz");
+        assert_eq!(weight(ListWidth::Narrow), "5 lb 12 oz");
+    }
+
+    #[test]
+    fn display_joins_with_commas() {
+        let items = NAMES.iter().map(|item| item.to_string()).collect();
+        let value = ListValue::new(items, ListStyle::Or, ListWidth::Narrow);
+        assert_eq!(value.to_string(), "Alice, Bob, Carol");
+    }
+}
@@ -0,0 +1,589 @@
+use super::InterpolationType;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    /// `#` inside of a plural case, replaced by the formatted number
+    Pound,
+    Arg(String),
+    Number(String),
+    /// `{name, date, style}` or `{name, time, style}`
+    DateTime {
+        arg: String,
+        time: bool,
+        style: String,
+    },
+    Plural {
+        arg: String,
+        ordinal: bool,
+        offset: f64,
+        cases: Vec<(String, Vec<Part>)>,
+    },
+    Select {
+        arg: String,
+        cases: Vec<(String, Vec<Part>)>,
+    },
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+        }
+    }
+
+    /// Parses a message until the end of the input or an unmatched `}`
+    fn parse_message(&mut self, in_plural: bool) -> Result<Vec<Part>, String> {
+        let mut parts = vec![];
+        let mut text = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            match ch {
+                '}' => break,
+                '{' => {
+                    self.chars.next();
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(self.parse_argument()?);
+                }
+                '#' if in_plural => {
+                    self.chars.next();
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Pound);
+                }
+                '\'' => {
+                    self.chars.next();
+                    self.parse_quoted(&mut text, in_plural);
+                }
+                _ => {
+                    self.chars.next();
+                    text.push(ch);
+                }
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(parts)
+    }
+
+    /// Handles the character(s) following an apostrophe
+    fn parse_quoted(&mut self, text: &mut String, in_plural: bool) {
+        match self.chars.peek() {
+            Some('\'') => {
+                self.chars.next();
+                text.push('\'');
+            }
+            Some('{' | '}' | '|') => self.read_quoted(text),
+            Some('#') if in_plural => self.read_quoted(text),
+            _ => text.push('\''),
+        }
+    }
+
+    /// Reads the literal text until the closing apostrophe, `''` is an escaped apostrophe
+    fn read_quoted(&mut self, text: &mut String) {
+        while let Some(ch) = self.chars.next() {
+            if ch == '\'' {
+                if self.chars.peek() == Some(&'\'') {
+                    self.chars.next();
+                    text.push('\'');
+                } else {
+                    return;
+                }
+            } else {
+                text.push(ch);
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+    }
+
+    fn read_word(&mut self) -> String {
+        self.skip_whitespace();
+        let mut word = String::new();
+        while let Some(ch) = self
+            .chars
+            .next_if(|ch| !ch.is_whitespace() && !matches!(ch, '{' | '}' | ','))
+        {
+            word.push(ch);
+        }
+        word
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(ch) if ch == expected => Ok(()),
+            Some(ch) => Err(format!("Expected '{}', found '{}'", expected, ch)),
+            None => Err(format!(
+                "Expected '{}', found the end of the message",
+                expected
+            )),
+        }
+    }
+
+    /// Parses an argument after its opening `{`, up to and including its closing `}`
+    fn parse_argument(&mut self) -> Result<Part, String> {
+        let arg = self.read_word();
+        if arg.is_empty() {
+            return Err("Missing argument name".to_string());
+        }
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('}') => return Ok(Part::Arg(arg)),
+            Some(',') => {}
+            Some(ch) => return Err(format!("Unexpected '{}' after argument {}", ch, arg)),
+            None => return Err(format!("Unclosed argument {}", arg)),
+        }
+
+        let kind = self.read_word();
+        let part = match kind.as_str() {
+            "plural" | "selectordinal" => {
+                self.expect(',')?;
+                let offset = self.parse_offset()?;
+                Part::Plural {
+                    arg,
+                    ordinal: kind == "selectordinal",
+                    offset,
+                    cases: self.parse_cases(true)?,
+                }
+            }
+            "select" => {
+                self.expect(',')?;
+                Part::Select {
+                    arg,
+                    cases: self.parse_cases(false)?,
+                }
+            }
+            "number" => {
+                self.read_style();
+                Part::Number(arg)
+            }
+            "date" | "time" => Part::DateTime {
+                arg,
+                time: kind == "time",
+                style: self.read_style(),
+            },
+            _ => {
+                // Unsupported types (duration, spellout, etc.) are formatted as plain arguments
+                self.read_style();
+                Part::Arg(arg)
+            }
+        };
+        self.expect('}')?;
+        Ok(part)
+    }
+
+    /// Parses an optional `offset:N` of a plural argument
+    fn parse_offset(&mut self) -> Result<f64, String> {
+        self.skip_whitespace();
+        let mut lookahead = self.chars.clone();
+        let keyword: String = lookahead.by_ref().take(7).collect();
+        if keyword != "offset:" {
+            return Ok(0.);
+        }
+        self.chars = lookahead;
+        let offset = self.read_word();
+        offset
+            .parse()
+            .map_err(|_| format!("Invalid plural offset: {}", offset))
+    }
+
+    /// Reads the optional style of an argument, e.g. `short` in `{d, date, short}`
+    fn read_style(&mut self) -> String {
+        self.skip_whitespace();
+        let mut style = String::new();
+        if self.chars.next_if_eq(&',').is_some() {
+            while let Some(ch) = self.chars.next_if(|ch| *ch != '}') {
+                style.push(ch);
+            }
+        }
+        style.trim().to_string()
+    }
+
+    /// Parses the `selector {message}` cases of a plural or select argument
+    fn parse_cases(&mut self, in_plural: bool) -> Result<Vec<(String, Vec<Part>)>, String> {
+        let mut cases = vec![];
+        loop {
+            self.skip_whitespace();
+            if matches!(self.chars.peek(), Some('}') | None) {
+                break;
+            }
+            let selector = self.read_word();
+            if selector.is_empty() {
+                return Err("Missing case selector".to_string());
+            }
+            self.expect('{')?;
+            let message = self.parse_message(in_plural)?;
+            self.expect('}')?;
+            cases.push((selector, message));
+        }
+        if !cases.iter().any(|(selector, _)| selector == "other") {
+            return Err("Missing 'other' case".to_string());
+        }
+        Ok(cases)
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Part>, String> {
+    let mut parser = Parser::new(source);
+    let parts = parser.parse_message(false)?;
+    match parser.chars.next() {
+        Some(ch) => Err(format!("Unexpected '{}'", ch)),
+        None => Ok(parts),
+    }
+}
+
+/// State shared while evaluating a message
+struct Evaluator<'a> {
+    #[cfg(any(feature = "numbers", feature = "datetime", feature = "lists"))]
+    locale: &'a str,
+    args: &'a [(String, InterpolationType)],
+    #[cfg(feature = "numbers")]
+    formatter: std::rc::Rc<icu_decimal::FixedDecimalFormatter>,
+}
+
+impl Evaluator<'_> {
+    fn arg(&self, name: &str) -> Option<&InterpolationType> {
+        self.args
+            .iter()
+            .find_map(|(k, v)| if k == name { Some(v) } else { None })
+    }
+
+    fn format_value(&self, value: &InterpolationType) -> String {
+        match value {
+            InterpolationType::String(v) | InterpolationType::Select(v) => v.clone(),
+            #[cfg(feature = "numbers")]
+            InterpolationType::Number(v) => v.format_to_string(self.locale),
+            #[cfg(feature = "datetime")]
+            InterpolationType::DateTime(v) => v.format_to_string(self.locale),
+            #[cfg(feature = "currency")]
+            InterpolationType::Currency(v) => v.format_to_string(self.locale),
+            #[cfg(feature = "relative_time")]
+            InterpolationType::RelativeTime(v) => v.format_to_string(self.locale),
+            #[cfg(feature = "lists")]
+            InterpolationType::List(v) => v.format_to_string(self.locale),
+            InterpolationType::Input(v) => v.to_string(),
+        }
+    }
+
+    /// Formats a date time argument with the length of the style, other arguments and styles are
+    /// formatted like plain arguments
+    #[cfg(feature = "datetime")]
+    fn format_date_time(&self, value: &InterpolationType, time: bool, style: &str) -> String {
+        use super::{DateTimeFormat, DateTimeLength, DateTimeValue};
+
+        let InterpolationType::DateTime(value) = value else {
+            return self.format_value(value);
+        };
+        let length = match style {
+            "full" => DateTimeLength::Full,
+            "long" => DateTimeLength::Long,
+            "" | "medium" => DateTimeLength::Medium,
+            "short" => DateTimeLength::Short,
+            _ => return value.format_to_string(self.locale),
+        };
+        let format = if time {
+            DateTimeFormat::Time(length)
+        } else {
+            DateTimeFormat::Date(length)
+        };
+        DateTimeValue::new(value.datetime, format).format_to_string(self.locale)
+    }
+
+    #[cfg(not(feature = "datetime"))]
+    fn format_date_time(&self, value: &InterpolationType, _time: bool, _style: &str) -> String {
+        self.format_value(value)
+    }
+
+    /// Returns the numeric value of an argument, string arguments are parsed, non-finite values are ignored
+    fn number(&self, name: &str) -> Option<f64> {
+        let value = match self.arg(name)? {
+            InterpolationType::String(v) | InterpolationType::Select(v) => v.trim().parse().ok(),
+            #[cfg(feature = "numbers")]
+            InterpolationType::Number(v) => v.to_string().parse().ok(),
+            #[cfg(feature = "datetime")]
+            InterpolationType::DateTime(_) => None,
+            #[cfg(feature = "currency")]
+            InterpolationType::Currency(v) => v.amount.to_string().parse().ok(),
+            #[cfg(feature = "relative_time")]
+            InterpolationType::RelativeTime(_) => None,
+            #[cfg(feature = "lists")]
+            InterpolationType::List(_) => None,
+            InterpolationType::Input(_) => None,
+        };
+        value.filter(|value: &f64| value.is_finite())
+    }
+
+    fn write(&self, parts: &[Part], pound: Option<&str>, output: &mut String) {
+        for part in parts {
+            match part {
+                Part::Text(text) => output.push_str(text),
+                Part::Pound => output.push_str(pound.unwrap_or("#")),
+                Part::Arg(name) | Part::Number(name) => {
+                    self.write_arg(name, output, |value| self.format_value(value))
+                }
+                Part::DateTime { arg, time, style } => self.write_arg(arg, output, |value| {
+                    self.format_date_time(value, *time, style)
+                }),
+                Part::Select { arg, cases } => {
+                    let value = self.arg(arg).map(ToString::to_string);
+                    let message = value
+                        .and_then(|value| find_case(cases, &value))
+                        .or_else(|| find_case(cases, "other"));
+                    if let Some(message) = message {
+                        self.write(message, pound, output);
+                    }
+                }
+                Part::Plural {
+                    arg,
+                    ordinal,
+                    offset,
+                    cases,
+                } => {
+                    let Some(value) = self.number(arg) else {
+                        bevy::log::warn!("Missing or invalid plural argument: {}", arg);
+                        if let Some(message) = find_case(cases, "other") {
+                            self.write(message, None, output);
+                        }
+                        continue;
+                    };
+                    let exact = cases.iter().find_map(|(selector, message)| {
+                        let selector = selector.strip_prefix('=')?.parse::<f64>().ok()?;
+                        (selector == value).then_some(message)
+                    });
+                    let message = exact
+                        .or_else(|| {
+                            find_case(cases, self.plural_category(value - offset, *ordinal))
+                        })
+                        .or_else(|| find_case(cases, "other"));
+                    if let Some(message) = message {
+                        let pound = self.format_number(value - offset);
+                        self.write(message, Some(&pound), output);
+                    }
+                }
+            }
+        }
+    }
+
+    fn write_arg(
+        &self,
+        name: &str,
+        output: &mut String,
+        format: impl Fn(&InterpolationType) -> String,
+    ) {
+        match self.arg(name) {
+            Some(value) => output.push_str(&format(value)),
+            None => {
+                bevy::log::warn!("Missing message format argument: {}", name);
+                output.push_str(&format!("{{{}}}", name));
+            }
+        }
+    }
+
+    #[cfg(feature = "numbers")]
+    fn plural_category(&self, value: f64, ordinal: bool) -> &'static str {
+        let rule_type = if ordinal {
+            icu_plurals::PluralRuleType::Ordinal
+        } else {
+            icu_plurals::PluralRuleType::Cardinal
+        };
+        let rules = super::utils::get_plural_rules(self.locale, value, rule_type);
+        super::utils::plural_suffix(rules.category_for(&super::utils::f64_to_fd(value)))
+    }
+
+    #[cfg(not(feature = "numbers"))]
+    fn plural_category(&self, _value: f64, _ordinal: bool) -> &'static str {
+        "other"
+    }
+
+    #[cfg(feature = "numbers")]
+    fn format_number(&self, value: f64) -> String {
+        self.formatter
+            .format_to_string(&super::utils::f64_to_fd(value))
+    }
+
+    #[cfg(not(feature = "numbers"))]
+    fn format_number(&self, value: f64) -> String {
+        value.to_string()
+    }
+}
+
+fn find_case<'a>(cases: &'a [(String, Vec<Part>)], selector: &str) -> Option<&'a Vec<Part>> {
+    cases
+        .iter()
+        .find_map(|(s, message)| if s == selector { Some(message) } else { None })
+}
+
+/// Evaluates an [ICU MessageFormat](https://unicode-org.github.io/icu/userguide/format_parse/messages/)
+/// message with the provided arguments
+///
+/// Supported arguments:
+/// - `{name}` and `{name, number}`
+/// - `{name, date, short}` and `{name, time, short}`, with a `short`, `medium`, `long` or `full` style
+///   for date time arguments
+/// - `{name, plural, =0 {...} one {# item} other {# items}}`, including `offset:N`
+/// - `{name, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}`
+/// - `{name, select, female {...} male {...} other {...}}`
+///
+/// Other argument types and styles, e.g. `{name, number, percent}` or `{name, date, ::yMMMd}`, are
+/// formatted like `{name}`, and plural arguments that aren't finite numbers use the `other` case
+///
+/// Apostrophes quote syntax characters, `'{'` is a literal `{` and `''` is a literal `'`
+///
+/// Returns the message untouched if it can't be parsed
+pub(super) fn format_message(
+    locale: &str,
+    message: &str,
+    args: &[(String, InterpolationType)],
+) -> String {
+    let parts = match parse(message) {
+        Ok(parts) => parts,
+        Err(err) => {
+            bevy::log::error!("Invalid message format \"{}\": {}", message, err);
+            return message.to_string();
+        }
+    };
+
+    #[cfg(not(any(feature = "numbers", feature = "datetime", feature = "lists")))]
+    let _ = locale;
+    let evaluator = Evaluator {
+        #[cfg(any(feature = "numbers", feature = "datetime", feature = "lists"))]
+        locale,
+        args,
+        #[cfg(feature = "numbers")]
+        formatter: super::utils::get_formatter(locale, message),
+    };
+    let mut output = String::new();
+    evaluator.write(&parts, None, &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(message: &str, args: &[(&str, &str)]) -> String {
+        let args: Vec<_> = args
+            .iter()
+            .map(|(k, v)| (k.to_string(), InterpolationType::from(*v)))
+            .collect();
+        format_message("en", message, &args)
+    }
+
+    #[test]
+    fn nested_select_and_plural() {
+        let message = "{gender, select, female {{n, plural, one {She has # cat} other {She has # cats}}} other {{n, plural, one {They have # cat} other {They have # cats}}}}";
+        assert_eq!(
+            format(message, &[("gender", "female"), ("n", "3")]),
+            "She has 3 cats"
+        );
+        assert_eq!(
+            format(message, &[("gender", "robot"), ("n", "3")]),
+            "They have 3 cats"
+        );
+    }
+
+    #[test]
+    fn apostrophes_escape_syntax() {
+        assert_eq!(format("It''s {name}", &[("name", "Bob")]), "It's Bob");
+        assert_eq!(
+            format("'{name}' is {name}", &[("name", "Bob")]),
+            "{name} is Bob"
+        );
+        assert_eq!(format("Don't", &[]), "Don't");
+        assert_eq!(
+            format("{n, plural, other {'#' is #}}", &[("n", "5")]),
+            "# is 5"
+        );
+    }
+
+    #[test]
+    fn exact_matches_and_offset() {
+        let message = "{n, plural, offset:1 =0 {Nobody} =1 {Only {host}} one {{host} and # other} other {{host} and # others}}";
+        assert_eq!(format(message, &[("n", "0"), ("host", "Ann")]), "Nobody");
+        assert_eq!(format(message, &[("n", "1"), ("host", "Ann")]), "Only Ann");
+        assert_eq!(
+            format(message, &[("n", "4"), ("host", "Ann")]),
+            "Ann and 3 others"
+        );
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn plural_categories_and_pound() {
+        let message = "{n, plural, one {# item} other {# items}}";
+        assert_eq!(format(message, &[("n", "1")]), "1 item");
+        assert_eq!(format(message, &[("n", "1234")]), "1,234 items");
+        assert_eq!(
+            format(
+                "{n, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}",
+                &[("n", "22")]
+            ),
+            "22nd"
+        );
+    }
+
+    #[test]
+    fn non_finite_plural_arguments_use_other() {
+        let message = "{n, plural, one {# item} other {some items}}";
+        assert_eq!(format(message, &[("n", "NaN")]), "some items");
+        assert_eq!(format(message, &[("n", "inf")]), "some items");
+        assert_eq!(format(message, &[("n", "-infinity")]), "some items");
+    }
+
+    #[test]
+    fn unsupported_types_are_plain_arguments() {
+        assert_eq!(
+            format(
+                "{n, number, percent} and {d, duration}",
+                &[("n", "5"), ("d", "10")]
+            ),
+            "5 and 10"
+        );
+        assert_eq!(format("{missing}", &[]), "{missing}");
+    }
+
+    #[test]
+    fn malformed_messages_are_untouched() {
+        for message in [
+            "{name",
+            "{}",
+            "{n, plural, one {# item}}",
+            "{n, plural, other {# items}",
+            "{n, plural, offset:x other {#}}",
+            "{n, select, other}",
+            "unmatched }",
+        ] {
+            assert!(parse(message).is_err(), "{}", message);
+            assert_eq!(format(message, &[("n", "1")]), message);
+        }
+    }
+
+    #[cfg(feature = "datetime")]
+    #[test]
+    fn date_and_time_styles() {
+        let value = super::super::DateTimeValue::new(
+            icu_calendar::DateTime::try_new_iso_datetime(2020, 1, 21, 8, 25, 7).unwrap(),
+            Default::default(),
+        );
+        let args = [("d".to_string(), InterpolationType::DateTime(value))];
+        assert_eq!(
+            format_message("en", "{d, date, long} at {d, time, short}", &args),
+            "January 21, 2020 at 8:25\u{202f}AM"
+        );
+        assert_eq!(format_message("en", "{d, date}", &args), "Jan 21, 2020");
+        assert_eq!(
+            format_message("en", "{d, date, ::yMMMd}", &args),
+            "Jan 21, 2020, 8:25\u{202f}AM"
+        );
+    }
+}
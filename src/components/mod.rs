@@ -1,20 +1,156 @@
+mod bound_args;
+#[cfg(feature = "currency")]
+mod i18n_currency;
+#[cfg(feature = "datetime")]
+mod i18n_date_time;
 mod i18n_font;
+#[cfg(feature = "units")]
+mod i18n_measure;
 #[cfg(feature = "numbers")]
 mod i18n_number;
+#[cfg(feature = "relative_time")]
+mod i18n_relative_time;
 mod i18n_text;
 mod i18n_text_2d;
+mod i18n_text_span;
+mod input_prompts;
+#[cfg(feature = "lists")]
+mod list_format;
+mod message_format;
+#[cfg(feature = "numbers")]
+mod number_format;
+mod rich_text;
+mod text_translation;
 mod utils;
 
+#[cfg(feature = "currency")]
+pub use i18n_currency::*;
+#[cfg(feature = "datetime")]
+pub use i18n_date_time::*;
 pub use i18n_font::*;
+#[cfg(feature = "units")]
+pub use i18n_measure::*;
 #[cfg(feature = "numbers")]
 pub use i18n_number::*;
+#[cfg(feature = "relative_time")]
+pub use i18n_relative_time::*;
 pub use i18n_text::*;
 pub use i18n_text_2d::*;
+pub use i18n_text_span::*;
+pub use input_prompts::*;
+#[cfg(feature = "lists")]
+pub use list_format::*;
+#[cfg(feature = "numbers")]
+pub use number_format::*;
+pub use rich_text::*;
+
+pub(crate) use bound_args::{apply_bound_args, read_bound_args, BoundArgUpdates, HasBoundArgs};
+#[cfg(feature = "relative_time")]
+pub(crate) use i18n_relative_time::HasRelativeTime;
+
+use bevy::{
+    ecs::{component::Component, entity::Entity, world::World},
+    text::{Text2d, TextSpan},
+    ui::widget::Text,
+    utils::SystemTime,
+};
+
+use crate::resources::I18n;
 
 pub trait I18nComponent {
-    /// If set, returns the locale of the component, otherwise the global locale
-    fn locale(&self) -> String;
+    /// Component of the entity that displays the translation, e.g. `Text`, `Text2d` or `TextSpan`
+    type Target: TranslationTarget;
+
+    /// If set, returns the locale of the component, otherwise the current locale of the [I18n] resource
+    fn locale(&self, i18n: &I18n) -> String;
+
+    /// Internal method that translates the component with the [crate::backend::TranslationBackend] of the [I18n] resource
+    fn translate(&self, i18n: &I18n) -> String;
+
+    /// Returns `true` if the component has its own locale, it then isn't translated again when the current
+    /// locale of the [I18n] resource changes
+    fn has_fixed_locale(&self) -> bool {
+        false
+    }
+
+    /// Returns a hash of the parts of the translation that change as time elapses, e.g. `3 minutes ago`
+    ///
+    /// The component is translated again whenever it changes, `None` for translations that don't depend on time
+    fn time_state(&self, _now: SystemTime) -> Option<u64> {
+        None
+    }
+
+    /// Returns the current values of the interpolation arguments bound to ECS data that changed, empty if
+    /// none of them did
+    ///
+    /// They're passed to [I18nComponent::set_bound_args], and the component is then translated again
+    fn bound_args(&self, _world: &World, _entity: Entity) -> Vec<(String, InterpolationType)> {
+        vec![]
+    }
+
+    /// Sets the values of bound interpolation arguments returned by [I18nComponent::bound_args]
+    fn set_bound_args(&mut self, _args: Vec<(String, InterpolationType)>) {}
+}
+
+/// Component that displays the translation of an [I18nComponent], updated by the systems of
+/// [crate::plugin::I18nComponentRegistration::register_i18n_component]
+///
+/// Implemented for `Text`, `Text2d` and `TextSpan`, implement it for the text components of your UI kit
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// #[derive(Component)]
+/// struct Label {
+///     text: String,
+/// }
+///
+/// impl TranslationTarget for Label {
+///     fn text(&self) -> &str {
+///         &self.text
+///     }
+///
+///     fn set_text(&mut self, text: String) {
+///         self.text = text;
+///     }
+/// }
+/// ```
+pub trait TranslationTarget: Component {
+    /// Returns the displayed text
+    fn text(&self) -> &str;
+
+    /// Replaces the displayed text
+    fn set_text(&mut self, text: String);
+}
+
+impl TranslationTarget for Text {
+    fn text(&self) -> &str {
+        &self.0
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.0 = text;
+    }
+}
+
+impl TranslationTarget for Text2d {
+    fn text(&self) -> &str {
+        &self.0
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.0 = text;
+    }
+}
+
+impl TranslationTarget for TextSpan {
+    fn text(&self) -> &str {
+        &self.0
+    }
 
-    /// Internal method that wraps the `rust_i18n::t!` macro
-    fn translate(&self) -> String;
+    fn set_text(&mut self, text: String) {
+        self.0 = text;
+    }
 }
@@ -0,0 +1,578 @@
+use bevy::reflect::Reflect;
+use fixed_decimal::FixedDecimal;
+use icu_decimal::{
+    options::{FixedDecimalFormatterOptions, GroupingStrategy},
+    FixedDecimalFormatter,
+};
+#[cfg(feature = "notations")]
+use icu_experimental::dimension::provider::percent::PercentEssentialsV1Marker;
+#[cfg(feature = "notations")]
+use icu_provider::{DataProvider, DataRequest};
+
+use super::utils;
+
+/// How a number is rounded to its maximum fraction digits
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum RoundingMode {
+    /// Round half to the nearest even digit, `2.5` -> `2`, `3.5` -> `4`
+    #[default]
+    HalfEven,
+    /// Round half away from zero, `2.5` -> `3`, `-2.5` -> `-3`
+    HalfExpand,
+    /// Round half toward zero, `2.5` -> `2`, `-2.5` -> `-2`
+    HalfTrunc,
+    /// Round toward positive infinity, `2.1` -> `3`, `-2.9` -> `-2`
+    Ceil,
+    /// Round toward negative infinity, `2.9` -> `2`, `-2.1` -> `-3`
+    Floor,
+    /// Round away from zero, `2.1` -> `3`, `-2.1` -> `-3`
+    Expand,
+    /// Round toward zero, `2.9` -> `2`, `-2.9` -> `-2`
+    Trunc,
+}
+
+/// When grouping separators are displayed, `1,000,000`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Grouping {
+    /// Use the grouping strategy of the locale
+    #[default]
+    Auto,
+    /// Never display grouping separators
+    Never,
+    /// Always display grouping separators
+    ///
+    /// ICU4X doesn't override the minimum grouping digits of the locale yet, so `1234` is still displayed
+    /// without separator in `es`, like [Grouping::Auto]
+    Always,
+    /// Only display grouping separators when there are at least 2 digits before the first separator,
+    /// `1000` but `10,000`
+    Min2,
+}
+
+/// When the sign of a number is displayed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SignDisplay {
+    /// Only display the sign of negative numbers, including negative zero
+    #[default]
+    Auto,
+    /// Never display the sign
+    Never,
+    /// Always display the sign, including on zero
+    Always,
+    /// Display the sign of positive and negative numbers, but not on zero, `+1`, `0`, `-1`
+    ExceptZero,
+    /// Only display the sign of negative numbers, excluding negative zero
+    Negative,
+}
+
+/// How a number is written, the symbols and words come from the CLDR data of the locale
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[non_exhaustive]
+pub enum Notation {
+    /// `1,234.5`
+    #[default]
+    Standard,
+    /// The number is a ratio displayed as a percentage, `0.45` -> `45%` in `en` or `45 %` in `fr`
+    ///
+    /// The fraction digits options apply to the percentage
+    #[cfg(feature = "notations")]
+    Percent,
+    /// `1,234,567` is `1.2M` in `en`, `1,2 Mio.` in `de` or `123万` in `ja`
+    ///
+    /// Without maximum fraction digits, the number is rounded to 2 significant digits below 100 (`1.2K`),
+    /// and to an integer above (`123K`). The other options apply to the displayed number
+    #[cfg(feature = "notations")]
+    CompactShort,
+    /// `1,234,567` is `1.2 million` in `en`, `1,2 Millionen` in `de` or `123万` in `ja`
+    ///
+    /// Rounded like [Notation::CompactShort]
+    #[cfg(feature = "notations")]
+    CompactLong,
+    /// One integer digit and a power of 10, `1.2345E3`
+    ///
+    /// The options apply to the digits before `E`, and the numbers use the separators and the digits of the
+    /// locale, `1,2345E3` in `de`. The exponent symbol is always `E`, ICU4X doesn't provide the symbols of
+    /// the locales yet
+    Scientific,
+    /// Like [Notation::Scientific] with an exponent multiple of 3, `12.345E3`
+    Engineering,
+}
+
+/// Options for formatting a number, shared by [super::I18nNumber] and the number interpolation arguments
+///
+/// # Example
+///
+/// ```
+/// use bevy_simple_i18n::prelude::*;
+///
+/// // `+12.50` for a stat delta
+/// let delta = NumberFormat::new()
+///     .with_fraction_digits(2)
+///     .with_sign_display(SignDisplay::ExceptZero);
+///
+/// // `007` for a timer, without grouping separators
+/// let timer = NumberFormat::new()
+///     .with_min_integer_digits(3)
+///     .with_grouping(Grouping::Never);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct NumberFormat {
+    notation: Notation,
+    min_fraction_digits: u8,
+    max_fraction_digits: Option<u8>,
+    min_integer_digits: u8,
+    rounding: RoundingMode,
+    grouping: Grouping,
+    sign_display: SignDisplay,
+}
+
+impl NumberFormat {
+    /// Creates a new `NumberFormat`, the number is displayed with the digits it has and the locale defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how the number is written, [Notation::Standard] by default
+    pub fn with_notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Display exactly this number of fraction digits, rounding or padding with zeros as needed
+    pub fn with_fraction_digits(mut self, digits: u8) -> Self {
+        self.min_fraction_digits = digits;
+        self.max_fraction_digits = Some(digits);
+        self
+    }
+
+    /// Pad the fraction with zeros up to this number of digits
+    pub fn with_min_fraction_digits(mut self, digits: u8) -> Self {
+        self.min_fraction_digits = digits;
+        self
+    }
+
+    /// Round the number to this number of fraction digits, see [NumberFormat::with_rounding]
+    pub fn with_max_fraction_digits(mut self, digits: u8) -> Self {
+        self.max_fraction_digits = Some(digits);
+        self
+    }
+
+    /// Pad the integer with zeros up to this number of digits, `7` -> `007`
+    pub fn with_min_integer_digits(mut self, digits: u8) -> Self {
+        self.min_integer_digits = digits;
+        self
+    }
+
+    /// Set how the number is rounded to its maximum fraction digits, [RoundingMode::HalfEven] by default
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Set when grouping separators are displayed, [Grouping::Auto] by default
+    pub fn with_grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Set when the sign is displayed, [SignDisplay::Auto] by default
+    pub fn with_sign_display(mut self, sign_display: SignDisplay) -> Self {
+        self.sign_display = sign_display;
+        self
+    }
+
+    /// Rounds and pads the number, and applies its sign display
+    ///
+    /// The result is also used to select plural forms, so `1.00` uses the same plural form as the displayed text
+    pub(crate) fn apply(&self, value: &FixedDecimal) -> FixedDecimal {
+        let mut value = value.clone();
+        self.round(&mut value);
+        self.pad_and_sign(&mut value);
+        value
+    }
+
+    /// Rounds the number to its maximum fraction digits, if any
+    fn round(&self, value: &mut FixedDecimal) {
+        if let Some(max) = self.max_fraction_digits {
+            let position = -i16::from(max);
+            match self.rounding {
+                RoundingMode::HalfEven => value.half_even(position),
+                RoundingMode::HalfExpand => value.half_expand(position),
+                RoundingMode::HalfTrunc => value.half_trunc(position),
+                RoundingMode::Ceil => value.ceil(position),
+                RoundingMode::Floor => value.floor(position),
+                RoundingMode::Expand => value.expand(position),
+                RoundingMode::Trunc => value.trunc(position),
+            }
+            // Rounding keeps the zeros up to the position, `2.96` -> `3.0`
+            value.trim_end();
+        }
+    }
+
+    fn pad_and_sign(&self, value: &mut FixedDecimal) {
+        value.pad_end(-i16::from(self.min_fraction_digits));
+        value.pad_start(i16::from(self.min_integer_digits));
+        value.apply_sign_display(match self.sign_display {
+            SignDisplay::Auto => fixed_decimal::SignDisplay::Auto,
+            SignDisplay::Never => fixed_decimal::SignDisplay::Never,
+            SignDisplay::Always => fixed_decimal::SignDisplay::Always,
+            SignDisplay::ExceptZero => fixed_decimal::SignDisplay::ExceptZero,
+            SignDisplay::Negative => fixed_decimal::SignDisplay::Negative,
+        });
+    }
+
+    pub(crate) fn formatter_options(&self) -> FixedDecimalFormatterOptions {
+        let mut options = FixedDecimalFormatterOptions::default();
+        options.grouping_strategy = match self.grouping {
+            Grouping::Auto => GroupingStrategy::Auto,
+            Grouping::Never => GroupingStrategy::Never,
+            Grouping::Always => GroupingStrategy::Always,
+            Grouping::Min2 => GroupingStrategy::Min2,
+        };
+        options
+    }
+}
+
+/// Number interpolation argument, already rounded and padded with its [NumberFormat]
+///
+/// Percentages are stored multiplied by 100, compact and scientific numbers are rounded when they are formatted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberValue {
+    pub(crate) value: FixedDecimal,
+    pub(crate) format: NumberFormat,
+}
+
+impl NumberValue {
+    pub(crate) fn new(value: &FixedDecimal, format: NumberFormat) -> Self {
+        let value = match format.notation {
+            Notation::Standard => format.apply(value),
+            #[cfg(feature = "notations")]
+            Notation::Percent => format.apply(&shifted(value, 2)),
+            _ => value.clone(),
+        };
+        Self { value, format }
+    }
+
+    /// Returns the number as a Fluent number, with the options that Fluent supports, `None` for the
+    /// notations other than [Notation::Standard]
+    #[cfg(feature = "fluent")]
+    pub(crate) fn to_fluent(&self) -> Option<fluent_bundle::types::FluentNumber> {
+        if self.format.notation != Notation::Standard {
+            return None;
+        }
+        let options = fluent_bundle::types::FluentNumberOptions {
+            use_grouping: self.format.grouping != Grouping::Never,
+            minimum_integer_digits: Some(self.format.min_integer_digits.into()),
+            minimum_fraction_digits: Some(self.format.min_fraction_digits.into()),
+            maximum_fraction_digits: self.format.max_fraction_digits.map(Into::into),
+            ..Default::default()
+        };
+        let value = self.value.to_string().parse().ok()?;
+        Some(fluent_bundle::types::FluentNumber::new(value, options))
+    }
+
+    /// Creates a number from a Fluent number, with the options that map to a [NumberFormat], `None` if
+    /// it isn't finite
+    #[cfg(feature = "fluent")]
+    pub(crate) fn from_fluent(number: &fluent_bundle::types::FluentNumber) -> Option<Self> {
+        if !number.value.is_finite() {
+            return None;
+        }
+        let options = &number.options;
+        let digits = |digits: usize| u8::try_from(digits).unwrap_or(u8::MAX);
+        let mut format = NumberFormat::new().with_grouping(if options.use_grouping {
+            Grouping::Auto
+        } else {
+            Grouping::Never
+        });
+        if let Some(min) = options.minimum_integer_digits {
+            format = format.with_min_integer_digits(digits(min));
+        }
+        if let Some(min) = options.minimum_fraction_digits {
+            format = format.with_min_fraction_digits(digits(min));
+        }
+        if let Some(max) = options.maximum_fraction_digits {
+            format = format.with_max_fraction_digits(digits(max));
+        }
+        Some(Self::new(&utils::f64_to_fd(number.value), format))
+    }
+
+    /// Formats the number for the provided locale
+    pub(crate) fn format_to_string(&self, locale: &str) -> String {
+        let formatter =
+            utils::get_formatter_with_options(locale, &self.value, self.format.formatter_options());
+        match self.format.notation {
+            Notation::Standard => formatter.format_to_string(&self.value),
+            #[cfg(feature = "notations")]
+            Notation::Percent => format_percent(locale, formatter.format_to_string(&self.value)),
+            #[cfg(feature = "notations")]
+            Notation::CompactShort => self.format_compact(locale, false),
+            #[cfg(feature = "notations")]
+            Notation::CompactLong => self.format_compact(locale, true),
+            Notation::Scientific => self.format_scientific(&formatter, 1),
+            Notation::Engineering => self.format_scientific(&formatter, 3),
+        }
+    }
+
+    /// Formats the number as a mantissa and an exponent multiple of `step`
+    fn format_scientific(&self, formatter: &FixedDecimalFormatter, step: i16) -> String {
+        let exponent_for = |magnitude: i16| magnitude.div_euclid(step) * step;
+        let mantissa_for = |exponent: i16| {
+            let mut mantissa = shifted(&self.value, -exponent);
+            self.format.round(&mut mantissa);
+            mantissa
+        };
+
+        let mut exponent = exponent_for(self.value.nonzero_magnitude_start());
+        let mut mantissa = mantissa_for(exponent);
+        // Rounding may carry over to the next exponent, `9.99E2` -> `10.0E2` -> `1.0E3`
+        let rounded_exponent = exponent_for(mantissa.nonzero_magnitude_start() + exponent);
+        if rounded_exponent != exponent {
+            exponent = rounded_exponent;
+            mantissa = mantissa_for(exponent);
+        }
+        self.format.pad_and_sign(&mut mantissa);
+
+        format!(
+            "{}E{}",
+            formatter.format_to_string(&mantissa),
+            formatter.format_to_string(&FixedDecimal::from(exponent))
+        )
+    }
+
+    /// Formats the number with the compact patterns of the locale
+    #[cfg(feature = "notations")]
+    fn format_compact(&self, locale: &str, long: bool) -> String {
+        let options = match self.format.grouping {
+            // Compact numbers only group from 10,000 by default
+            Grouping::Auto => self.format.with_grouping(Grouping::Min2),
+            _ => self.format,
+        }
+        .formatter_options();
+        let formatter = utils::get_compact_formatter(locale, &self.value, long, options);
+        let exponent_for =
+            |magnitude: i16| i16::from(formatter.compact_exponent_for_magnitude(magnitude));
+        let significand_for = |exponent: i16| {
+            let mut significand = shifted(&self.value, -exponent);
+            if self.format.max_fraction_digits.is_some() {
+                self.format.round(&mut significand);
+            } else {
+                // 2 significant digits below 10, `1.2K` or `0.12`, and whole numbers above
+                let start = significand.nonzero_magnitude_start();
+                let position = if start <= 0 { start - 1 } else { 0 };
+                significand.half_even(position);
+                significand.trim_end();
+            }
+            significand
+        };
+
+        let mut exponent = exponent_for(self.value.nonzero_magnitude_start());
+        let mut significand = significand_for(exponent);
+        // Rounding may carry over to the next exponent, `999.9K` -> `1000K` -> `1M`
+        let rounded_exponent = exponent_for(significand.nonzero_magnitude_start() + exponent);
+        if rounded_exponent != exponent {
+            exponent = rounded_exponent;
+            significand = significand_for(exponent);
+        }
+        self.format.pad_and_sign(&mut significand);
+
+        let compact = fixed_decimal::CompactDecimal::from_significand_and_exponent(
+            significand,
+            exponent as u8,
+        );
+        match formatter.format_compact_decimal(&compact) {
+            Ok(formatted) => formatted.to_string(),
+            Err(err) => {
+                bevy::log::error!("Failed to format compact number {}: {:?}", self.value, err);
+                self.value.to_string()
+            }
+        }
+    }
+}
+
+/// Multiplies the number by a power of 10 without keeping the zeros it moved, `0.45` -> `45`
+fn shifted(value: &FixedDecimal, power: i16) -> FixedDecimal {
+    let mut value = value.clone().multiplied_pow10(power);
+    value.trim_start();
+    value.trim_end();
+    value
+}
+
+/// Places the percent sign of the locale before or after the formatted number
+#[cfg(feature = "notations")]
+fn format_percent(locale: &str, number: String) -> String {
+    let data_locale = utils::resolve_locale(locale, &number).into();
+    let payload = DataProvider::<PercentEssentialsV1Marker>::load(
+        &icu_experimental::provider::Baked,
+        DataRequest {
+            locale: &data_locale,
+            metadata: Default::default(),
+        },
+    )
+    .and_then(|response| response.take_payload());
+    let payload = match payload {
+        Ok(payload) => payload,
+        Err(err) => {
+            bevy::log::error!("Missing percent data for locale {}: {}", locale, err);
+            return format!("{}%", number);
+        }
+    };
+    let essentials = payload.get();
+
+    let affixes = &essentials.percent_sign_affixes;
+    if essentials.percent_symbol_index < essentials.number_index {
+        format!(
+            "{}{}{}{}",
+            affixes.prefix, essentials.percent_sign_symbol, affixes.suffix, number
+        )
+    } else {
+        format!(
+            "{}{}{}{}",
+            number, affixes.prefix, essentials.percent_sign_symbol, affixes.suffix
+        )
+    }
+}
+
+/// Formats as the raw number, `1234.50`, or the percentage for [Notation::Percent]
+impl std::fmt::Display for NumberValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(value: f64, format: NumberFormat, locale: &str) -> String {
+        NumberValue::new(&utils::f64_to_fd(value), format).format_to_string(locale)
+    }
+
+    #[test]
+    fn fraction_and_integer_digits() {
+        let fixed = NumberFormat::new().with_fraction_digits(2);
+        assert_eq!(format(3., fixed, "en"), "3.00");
+        assert_eq!(format(2.996, fixed, "en"), "3.00");
+        assert_eq!(format(1234.5, fixed, "de"), "1.234,50");
+
+        let max = NumberFormat::new().with_max_fraction_digits(2);
+        assert_eq!(format(2.5, max, "en"), "2.5");
+        assert_eq!(format(2.996, max, "en"), "3");
+
+        let timer = NumberFormat::new().with_min_integer_digits(3);
+        assert_eq!(format(7., timer, "en"), "007");
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let round = |value: f64, rounding: RoundingMode| {
+            let format = NumberFormat::new()
+                .with_max_fraction_digits(0)
+                .with_rounding(rounding);
+            format!(
+                "{} {}",
+                self::format(value, format, "en"),
+                self::format(-value, format, "en")
+            )
+        };
+        assert_eq!(round(2.5, RoundingMode::HalfEven), "2 -2");
+        assert_eq!(round(3.5, RoundingMode::HalfEven), "4 -4");
+        assert_eq!(round(2.5, RoundingMode::HalfExpand), "3 -3");
+        assert_eq!(round(2.5, RoundingMode::HalfTrunc), "2 -2");
+        assert_eq!(round(2.1, RoundingMode::Ceil), "3 -2");
+        assert_eq!(round(2.9, RoundingMode::Floor), "2 -3");
+        assert_eq!(round(2.1, RoundingMode::Expand), "3 -3");
+        assert_eq!(round(2.9, RoundingMode::Trunc), "2 -2");
+    }
+
+    #[test]
+    fn grouping_strategies() {
+        let grouping = |value: f64, grouping: Grouping, locale: &str| {
+            format(value, NumberFormat::new().with_grouping(grouping), locale)
+        };
+        assert_eq!(grouping(1234567., Grouping::Auto, "en"), "1,234,567");
+        assert_eq!(grouping(1234567., Grouping::Never, "en"), "1234567");
+        assert_eq!(grouping(1234., Grouping::Min2, "en"), "1234");
+        assert_eq!(grouping(12345., Grouping::Min2, "en"), "12,345");
+        assert_eq!(grouping(1234., Grouping::Always, "en"), "1,234");
+        // Spanish only groups from 10,000
+        assert_eq!(grouping(1234., Grouping::Auto, "es"), "1234");
+        assert_eq!(grouping(12345., Grouping::Auto, "es"), "12.345");
+    }
+
+    #[test]
+    fn sign_display() {
+        let signs = |sign_display: SignDisplay| {
+            let format = NumberFormat::new().with_sign_display(sign_display);
+            [1., 0., -0., -1.]
+                .map(|value| self::format(value, format, "en"))
+                .join(" ")
+        };
+        assert_eq!(signs(SignDisplay::Auto), "1 0 -0 -1");
+        assert_eq!(signs(SignDisplay::Never), "1 0 0 1");
+        assert_eq!(signs(SignDisplay::Always), "+1 +0 -0 -1");
+        assert_eq!(signs(SignDisplay::ExceptZero), "+1 0 0 -1");
+        assert_eq!(signs(SignDisplay::Negative), "1 0 0 -1");
+    }
+
+    #[test]
+    fn scientific_and_engineering_notations() {
+        let scientific = NumberFormat::new().with_notation(Notation::Scientific);
+        assert_eq!(format(1234.5, scientific, "en"), "1.2345E3");
+        assert_eq!(format(0.00012, scientific, "en"), "1.2E-4");
+        assert_eq!(format(-5., scientific, "en"), "-5E0");
+        assert_eq!(format(1234.5, scientific, "de"), "1,2345E3");
+        assert_eq!(format(1234.5, scientific, "sv"), "1,2345E3");
+        assert_eq!(format(1234.5, scientific, "fa"), "۱٫۲۳۴۵E۳");
+        // Rounding carries over to the next exponent
+        let rounded = scientific.with_max_fraction_digits(1);
+        assert_eq!(format(9.96, rounded, "en"), "1E1");
+        assert_eq!(format(1234.5, rounded, "en"), "1.2E3");
+
+        let engineering = NumberFormat::new().with_notation(Notation::Engineering);
+        assert_eq!(format(12345., engineering, "en"), "12.345E3");
+        assert_eq!(format(123456., engineering, "en"), "123.456E3");
+        assert_eq!(format(0.0012, engineering, "en"), "1.2E-3");
+        assert_eq!(
+            format(999999., engineering.with_max_fraction_digits(0), "en"),
+            "1E6"
+        );
+    }
+
+    #[cfg(feature = "notations")]
+    #[test]
+    fn compact_notations() {
+        let short = NumberFormat::new().with_notation(Notation::CompactShort);
+        assert_eq!(format(999., short, "en"), "999");
+        assert_eq!(format(0.123, short, "en"), "0.12");
+        assert_eq!(format(0.45, short, "en"), "0.45");
+        assert_eq!(format(0.0456, short, "en"), "0.046");
+        assert_eq!(format(5., short, "en"), "5");
+        assert_eq!(format(1234., short, "en"), "1.2K");
+        assert_eq!(format(123456., short, "en"), "123K");
+        assert_eq!(format(999999., short, "en"), "1M");
+        assert_eq!(format(-1234567., short, "en"), "-1.2M");
+        assert_eq!(format(1234567., short, "de"), "1,2\u{a0}Mio.");
+        assert_eq!(format(1234567., short, "ja"), "123万");
+        assert_eq!(
+            format(1234567., short.with_max_fraction_digits(2), "en"),
+            "1.23M"
+        );
+
+        let long = NumberFormat::new().with_notation(Notation::CompactLong);
+        assert_eq!(format(1234567., long, "en"), "1.2 million");
+        assert_eq!(format(1234567., long, "de"), "1,2 Millionen");
+    }
+
+    #[cfg(feature = "notations")]
+    #[test]
+    fn percent_notation() {
+        let percent = NumberFormat::new().with_notation(Notation::Percent);
+        assert_eq!(format(0.45, percent, "en"), "45%");
+        assert_eq!(format(0.45, percent, "fr"), "45\u{a0}%");
+        assert_eq!(
+            format(0.1234, percent.with_fraction_digits(1), "en"),
+            "12.3%"
+        );
+    }
+}
@@ -0,0 +1,539 @@
+use bevy::{
+    asset::Handle,
+    color::{Color, Srgba},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        query::{Changed, Or, With},
+        reflect::{ReflectComponent, ReflectResource},
+        system::{Commands, Query, Res, Resource},
+    },
+    hierarchy::{BuildChildren, ChildBuild, Children, DespawnRecursiveExt},
+    image::Image,
+    reflect::Reflect,
+    text::{Font, TextColor, TextFont, TextSpan},
+    ui::{
+        widget::{ImageNode, Text},
+        AlignSelf, Node, Val,
+    },
+    utils::HashMap,
+};
+
+use super::InterpolationType;
+
+/// Style applied to the text inside a rich text tag, the fields that are `None` are inherited from
+/// the enclosing text
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// fn setup(mut styles: ResMut<RichTextStyles>) {
+///     styles
+///         .insert("b", RichTextStyle::new().with_font_size(24.))
+///         .insert("fire", RichTextStyle::new().with_color(Color::srgb(1., 0.4, 0.)));
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Reflect)]
+pub struct RichTextStyle {
+    /// Font of the text, e.g. a bold or italic variant
+    pub font: Option<Handle<Font>>,
+    /// Font size of the text
+    pub font_size: Option<f32>,
+    /// Color of the text
+    pub color: Option<Color>,
+}
+
+impl RichTextStyle {
+    /// Creates a new `RichTextStyle` that inherits everything from the enclosing text
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the font of the text
+    pub fn with_font(mut self, font: Handle<Font>) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Set the font size of the text
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Set the color of the text
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Returns this style with the fields that are `None` taken from the enclosing style
+    fn inherit(&self, parent: &RichTextStyle) -> RichTextStyle {
+        RichTextStyle {
+            font: self.font.clone().or_else(|| parent.font.clone()),
+            font_size: self.font_size.or(parent.font_size),
+            color: self.color.or(parent.color),
+        }
+    }
+}
+
+/// Icon placed in a rich text translation with an `<icon=name>` tag, e.g. a controller button
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// fn setup(mut styles: ResMut<RichTextStyles>, asset_server: Res<AssetServer>) {
+///     styles
+///         .insert_icon("a", RichTextIcon::Image(asset_server.load("icons/button_a.png")))
+///         .insert_icon(
+///             "b",
+///             RichTextIcon::Glyph {
+///                 font: asset_server.load("fonts/buttons.ttf"),
+///                 glyph: '\u{e001}',
+///             },
+///         );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub enum RichTextIcon {
+    /// Character of an icon font, e.g. a private-use glyph, styled like the enclosing text
+    Glyph {
+        /// Font containing the glyph
+        font: Handle<Font>,
+        /// Character of the glyph
+        glyph: char,
+    },
+    /// Image node the size of the enclosing text
+    ///
+    /// Bevy text can't contain images, so a translation with image icons is laid out as a row of `Text` and
+    /// `ImageNode` entities and doesn't wrap as a single paragraph. Use [RichTextIcon::Glyph] for text that wraps
+    Image(Handle<Image>),
+}
+
+/// Named styles and icons of the rich text tags, registered by the app
+///
+/// A translation evaluated as rich text, see [crate::components::I18nText::with_rich_text], can use:
+///
+/// - `<name>...</name>` to apply the style registered as `name`
+/// - `<color=name>...</color>` to apply the style registered as `name`, or a hex color, `<color=#ff6600>`
+/// - `<size=24>...</size>` to set the font size
+/// - `<icon=name>` to place the [RichTextIcon] registered as `name`
+///
+/// Tags can be nested, and the tags that aren't registered are displayed as they are. `\<` is displayed
+/// as `<` and `\\` as `\`, the string and list interpolation arguments are escaped this way so that
+/// user content, e.g. a player name, is never read as markup
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct RichTextStyles {
+    styles: HashMap<String, RichTextStyle>,
+    icons: HashMap<String, RichTextIcon>,
+}
+
+/// Part of a rich text, with its style relative to the root text
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RichTextPart {
+    /// Styled text
+    Span(String, RichTextStyle),
+    /// Image icon, sized with the font size of the style
+    Image(Handle<Image>, RichTextStyle),
+}
+
+/// Effect of a known rich text tag
+enum RichTextTag<'a> {
+    /// Opening tag, with its name and style
+    Open(&'a str, RichTextStyle),
+    /// Closing tag, with the index of the matching open tag
+    Close(usize),
+    /// Icon tag
+    Icon(RichTextIcon),
+}
+
+impl RichTextStyles {
+    /// Registers a named style, replacing the style with the same name if there is one
+    pub fn insert(&mut self, name: impl Into<String>, style: RichTextStyle) -> &mut Self {
+        self.styles.insert(name.into(), style);
+        self
+    }
+
+    /// Returns the style registered with the provided name
+    pub fn get(&self, name: &str) -> Option<&RichTextStyle> {
+        self.styles.get(name)
+    }
+
+    /// Removes the style registered with the provided name
+    pub fn remove(&mut self, name: &str) -> Option<RichTextStyle> {
+        self.styles.remove(name)
+    }
+
+    /// Registers a named icon, replacing the icon with the same name if there is one
+    pub fn insert_icon(&mut self, name: impl Into<String>, icon: RichTextIcon) -> &mut Self {
+        self.icons.insert(name.into(), icon);
+        self
+    }
+
+    /// Returns the icon registered with the provided name
+    pub fn get_icon(&self, name: &str) -> Option<&RichTextIcon> {
+        self.icons.get(name)
+    }
+
+    /// Removes the icon registered with the provided name
+    pub fn remove_icon(&mut self, name: &str) -> Option<RichTextIcon> {
+        self.icons.remove(name)
+    }
+
+    /// Returns the style of an opening tag, `None` if the tag isn't known
+    fn tag_style(&self, tag: &str) -> Option<RichTextStyle> {
+        match tag.split_once('=') {
+            Some(("color", value)) => self.get(value).cloned().or_else(|| {
+                let color = Srgba::hex(value.strip_prefix('#')?).ok()?;
+                Some(RichTextStyle::new().with_color(color))
+            }),
+            Some(("size", value)) => Some(RichTextStyle::new().with_font_size(value.parse().ok()?)),
+            Some(_) => None,
+            None => self.get(tag).cloned(),
+        }
+    }
+
+    /// Splits rich text markup into its spans and icons, with their style relative to the root text
+    pub(crate) fn parse(&self, markup: &str) -> Vec<RichTextPart> {
+        // Name of the open tags and their style, the innermost last
+        let mut open: Vec<(&str, RichTextStyle)> = vec![];
+        let mut parts: Vec<RichTextPart> = vec![];
+        let mut text = String::new();
+        let mut rest = markup;
+        while let Some(start) = rest.find(['<', '\\']) {
+            text.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            if rest[start..].starts_with('\\') {
+                // Other backslashes are displayed as they are
+                match after.chars().next() {
+                    Some(ch @ ('<' | '\\')) => {
+                        text.push(ch);
+                        rest = &after[1..];
+                    }
+                    _ => {
+                        text.push('\\');
+                        rest = after;
+                    }
+                }
+                continue;
+            }
+            let tag = after.find('>').map(|end| &after[..end]);
+            let style = open
+                .last()
+                .map(|(_, style)| style.clone())
+                .unwrap_or_default();
+            let change = tag.and_then(|tag| {
+                if let Some(name) = tag.strip_prefix('/') {
+                    let index = open.iter().rposition(|(open, _)| *open == name)?;
+                    return Some(RichTextTag::Close(index));
+                }
+                if let Some(name) = tag.strip_prefix("icon=") {
+                    return Some(RichTextTag::Icon(self.get_icon(name)?.clone()));
+                }
+                let name = tag.split_once('=').map_or(tag, |(name, _)| name);
+                Some(RichTextTag::Open(
+                    name,
+                    self.tag_style(tag)?.inherit(&style),
+                ))
+            });
+            let Some(change) = change else {
+                text.push('<');
+                rest = after;
+                continue;
+            };
+            if !text.is_empty() {
+                parts.push(RichTextPart::Span(std::mem::take(&mut text), style.clone()));
+            }
+            match change {
+                RichTextTag::Open(name, style) => open.push((name, style)),
+                RichTextTag::Close(index) => open.truncate(index),
+                RichTextTag::Icon(RichTextIcon::Glyph { font, glyph }) => {
+                    parts.push(RichTextPart::Span(glyph.to_string(), style.with_font(font)))
+                }
+                RichTextTag::Icon(RichTextIcon::Image(image)) => {
+                    parts.push(RichTextPart::Image(image, style))
+                }
+            }
+            rest = &after[tag.map_or(0, str::len) + 1..];
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            let style = open.pop().map(|(_, style)| style).unwrap_or_default();
+            parts.push(RichTextPart::Span(text, style));
+        }
+        parts
+    }
+}
+
+/// Escapes the rich text markup of a string, `<b>` is displayed as it is
+pub(crate) fn escape_markup(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('<', "\\<")
+}
+
+/// Escapes the string and list interpolation arguments of a rich text translation
+pub(crate) fn escape_args(
+    args: &[(String, InterpolationType)],
+) -> Vec<(String, InterpolationType)> {
+    args.iter()
+        .map(|(key, value)| {
+            let value = match value {
+                InterpolationType::String(text) => InterpolationType::String(escape_markup(text)),
+                #[cfg(feature = "lists")]
+                InterpolationType::List(list) => InterpolationType::List(super::ListValue {
+                    items: list.items.iter().map(|item| escape_markup(item)).collect(),
+                    ..list.clone()
+                }),
+                _ => value.clone(),
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+/// Translated rich text markup of an entity, its `Text` is rebuilt as styled `TextSpan` children
+/// whenever it changes
+#[derive(Debug, Default, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct RichTextMarkup(pub(crate) String);
+
+impl RichTextMarkup {
+    /// Returns the translated markup
+    pub fn markup(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Marker of the `TextSpan`, `Text` and `ImageNode` children created for a [RichTextMarkup]
+#[derive(Debug, Default, Clone, Copy, Component, Reflect)]
+#[reflect(Component)]
+pub struct RichTextSpan;
+
+/// Rich text entities whose markup or root style changed
+type ChangedRichText = Or<(
+    Changed<RichTextMarkup>,
+    Changed<TextFont>,
+    Changed<TextColor>,
+)>;
+
+/// Rebuilds the `TextSpan` children of the rich text entities whose markup, root style or registered
+/// styles changed
+///
+/// When the markup has image icons, the spans and images are laid out in a row instead, as `Text` and
+/// `ImageNode` children, and each of them wraps on its own
+pub(crate) fn update_rich_text(
+    mut commands: Commands,
+    styles: Res<RichTextStyles>,
+    roots: Query<(
+        Entity,
+        &RichTextMarkup,
+        &TextFont,
+        &TextColor,
+        Option<&Children>,
+    )>,
+    changed: Query<(), ChangedRichText>,
+    spans: Query<(), With<RichTextSpan>>,
+) {
+    for (entity, markup, font, color, children) in roots.iter() {
+        if !styles.is_changed() && !changed.contains(entity) {
+            continue;
+        }
+        for child in children.into_iter().flatten() {
+            if spans.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        let parts = styles.parse(&markup.0);
+        let inline = !parts
+            .iter()
+            .any(|part| matches!(part, RichTextPart::Image(..)));
+        let text_font = |style: RichTextStyle| TextFont {
+            font: style.font.unwrap_or_else(|| font.font.clone()),
+            font_size: style.font_size.unwrap_or(font.font_size),
+            ..font.clone()
+        };
+        commands.entity(entity).with_children(|parent| {
+            for part in parts {
+                match part {
+                    RichTextPart::Span(span, style) if inline => {
+                        let color = TextColor(style.color.unwrap_or(color.0));
+                        parent.spawn((TextSpan::new(span), text_font(style), color, RichTextSpan));
+                    }
+                    RichTextPart::Span(span, style) => {
+                        let color = TextColor(style.color.unwrap_or(color.0));
+                        parent.spawn((Text::new(span), text_font(style), color, RichTextSpan));
+                    }
+                    RichTextPart::Image(image, style) => {
+                        let size = Val::Px(style.font_size.unwrap_or(font.font_size));
+                        parent.spawn((
+                            ImageNode::new(image),
+                            Node {
+                                width: size,
+                                height: size,
+                                align_self: AlignSelf::Center,
+                                ..Default::default()
+                            },
+                            RichTextSpan,
+                        ));
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styles() -> RichTextStyles {
+        let mut styles = RichTextStyles::default();
+        styles
+            .insert("b", RichTextStyle::new().with_font_size(24.))
+            .insert("fire", RichTextStyle::new().with_color(Color::BLACK));
+        styles
+    }
+
+    fn span(text: &str, style: RichTextStyle) -> RichTextPart {
+        RichTextPart::Span(text.to_string(), style)
+    }
+
+    #[test]
+    fn nested_tags_inherit_the_enclosing_style() {
+        let bold = RichTextStyle::new().with_font_size(24.);
+        assert_eq!(
+            styles().parse("Deal <b>10 <fire>fire</fire></b> damage"),
+            [
+                span("Deal ", RichTextStyle::new()),
+                span("10 ", bold.clone()),
+                span("fire", bold.clone().with_color(Color::BLACK)),
+                span(" damage", RichTextStyle::new()),
+            ]
+        );
+        // Closing an outer tag closes the tags inside of it
+        assert_eq!(
+            styles().parse("<b>a<fire>b</b>c"),
+            [
+                span("a", bold.clone()),
+                span("b", bold.with_color(Color::BLACK)),
+                span("c", RichTextStyle::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn color_and_size_tags() {
+        assert_eq!(
+            styles().parse("<color=fire>a</color><color=#ff6600>b</color><size=12>c</size>"),
+            [
+                span("a", RichTextStyle::new().with_color(Color::BLACK)),
+                span(
+                    "b",
+                    RichTextStyle::new().with_color(Srgba::hex("ff6600").unwrap())
+                ),
+                span("c", RichTextStyle::new().with_font_size(12.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn unclosed_tags_style_the_rest_of_the_text() {
+        assert_eq!(
+            styles().parse("Hello <b>world"),
+            [
+                span("Hello ", RichTextStyle::new()),
+                span("world", RichTextStyle::new().with_font_size(24.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_and_malformed_tags_are_displayed() {
+        for markup in [
+            "<i>not registered</i>",
+            "a < b and b > a",
+            "</b> without opening",
+            "<color=nope>x</color>",
+            "<size=big>x</size>",
+            "trailing <b",
+        ] {
+            assert_eq!(
+                styles().parse(markup),
+                [span(markup, RichTextStyle::new())],
+                "{}",
+                markup
+            );
+        }
+        assert!(styles().parse("").is_empty());
+    }
+
+    #[test]
+    fn escaped_markup_is_displayed() {
+        let name = r"<b>Bob</b> \o/ \<";
+        let markup = format!("<b>Hi</b> {}", escape_markup(name));
+        assert_eq!(
+            styles().parse(&markup),
+            [
+                span("Hi", RichTextStyle::new().with_font_size(24.)),
+                span(&format!(" {}", name), RichTextStyle::new()),
+            ]
+        );
+        assert_eq!(
+            styles().parse(r"C:\path \<b> \\"),
+            [span(r"C:\path <b> \", RichTextStyle::new())]
+        );
+    }
+
+    #[test]
+    fn string_arguments_are_escaped() {
+        let args = escape_args(&[
+            ("name".to_string(), "<color=red>Bob".into()),
+            (
+                "mode".to_string(),
+                InterpolationType::Select("<b>".to_string()),
+            ),
+        ]);
+        assert_eq!(
+            args,
+            [
+                ("name".to_string(), r"\<color=red>Bob".into()),
+                (
+                    "mode".to_string(),
+                    InterpolationType::Select("<b>".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn icons_are_placed_with_the_enclosing_style() {
+        let font = Handle::<Font>::weak_from_u128(1);
+        let image = Handle::<Image>::weak_from_u128(2);
+        let mut styles = styles();
+        styles
+            .insert_icon(
+                "a",
+                RichTextIcon::Glyph {
+                    font: font.clone(),
+                    glyph: '\u{e001}',
+                },
+            )
+            .insert_icon("b", RichTextIcon::Image(image.clone()));
+        let bold = RichTextStyle::new().with_font_size(24.);
+        assert_eq!(
+            styles.parse("Press <b><icon=a></b> or <icon=b> to <icon=c>"),
+            [
+                span("Press ", RichTextStyle::new()),
+                span("\u{e001}", bold.with_font(font)),
+                span(" or ", RichTextStyle::new()),
+                RichTextPart::Image(image, RichTextStyle::new()),
+                span(" to <icon=c>", RichTextStyle::new()),
+            ]
+        );
+    }
+}
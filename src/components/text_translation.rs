@@ -0,0 +1,443 @@
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        component::{Component, ComponentId},
+        entity::Entity,
+        world::{DeferredWorld, World},
+    },
+    log::debug,
+    reflect::{Reflect, TypePath},
+};
+
+use crate::resources::I18n;
+
+use super::{
+    bound_args::{BoundArgs, HasBoundArgs},
+    rich_text::{escape_args, RichTextMarkup},
+    utils::{format_message_by_key, translate_by_key, translate_entity},
+    I18nComponent, InterpolationType, TranslationTarget,
+};
+
+/// Translation key, interpolation arguments and options shared by [super::I18nText], [super::I18nText2d]
+/// and [super::I18nTextSpan]
+#[derive(Default, Reflect, Debug, Clone)]
+pub(crate) struct TextTranslation {
+    /// Translation key for i18n
+    pub(crate) key: String,
+    /// Interpolation arguments for the translation key
+    pub(crate) args: Vec<(String, InterpolationType)>,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+    /// Evaluate the translation as an ICU MessageFormat message
+    pub(crate) message_format: bool,
+    /// Display the translation as rich text markup, only set by [super::I18nText::with_rich_text]
+    pub(crate) rich_text: bool,
+    /// Interpolation arguments bound to a component of the entity or to a resource
+    #[reflect(ignore)]
+    pub(crate) bindings: BoundArgs,
+}
+
+impl TextTranslation {
+    pub(crate) fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn locale(&self, i18n: &I18n) -> String {
+        self.locale
+            .clone()
+            .unwrap_or_else(|| i18n.current().to_string())
+    }
+
+    pub(crate) fn translate(&self, i18n: &I18n) -> String {
+        // The arguments of a rich text translation are displayed as they are, not as markup
+        let args = if self.rich_text {
+            Cow::Owned(escape_args(&self.args))
+        } else {
+            Cow::Borrowed(self.args.as_slice())
+        };
+        if self.message_format {
+            format_message_by_key(i18n, &self.locale(i18n), &self.key, &args)
+        } else {
+            translate_by_key(i18n, &self.locale(i18n), &self.key, &args)
+        }
+    }
+
+    /// Returns the current values of the bound arguments that changed
+    pub(crate) fn bound_args(
+        &self,
+        world: &World,
+        entity: Entity,
+    ) -> Vec<(String, InterpolationType)> {
+        self.bindings.resolve(&self.args, world, entity)
+    }
+
+    /// Sets the value of an argument, replacing the argument with the same key if there is one
+    pub(crate) fn set_arg(&mut self, key: String, value: InterpolationType) {
+        match self.args.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, current)) => *current = value,
+            None => self.args.push((key, value)),
+        }
+    }
+}
+
+/// Text component with a [TextTranslation], implemented by [impl_text_translation]
+pub(crate) trait TextComponent: I18nComponent + Component + Clone + TypePath {
+    fn translation(&self) -> &TextTranslation;
+}
+
+/// Translates a text component when it's added, the `on_add` hook of [super::I18nText], [super::I18nText2d]
+/// and [super::I18nTextSpan]
+///
+/// The bound arguments are read before the first translation, which replaces the text of the
+/// [I18nComponent::Target] or inserts it. Rich text is inserted as markup, its spans are built by
+/// [super::update_rich_text]
+pub(crate) fn translate_added<T: TextComponent>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) where
+    T::Target: Default,
+{
+    let mut val = world.get::<T>(entity).unwrap().clone();
+    let bound = val.bound_args(&world, entity);
+    if !bound.is_empty() {
+        val.set_bound_args(bound.clone());
+        world.get_mut::<T>(entity).unwrap().set_bound_args(bound);
+    }
+    if !val.translation().bindings.is_empty() {
+        world.commands().entity(entity).insert(HasBoundArgs);
+    }
+    debug!("Adding {}: {}", T::short_type_path(), val.translation().key);
+    let translated = translate_entity(&mut world, entity, &val);
+    if val.translation().rich_text {
+        world
+            .commands()
+            .entity(entity)
+            .insert((T::Target::default(), RichTextMarkup(translated)));
+    } else if let Some(mut target) = world.get_mut::<T::Target>(entity) {
+        target.set_text(translated);
+    } else {
+        let mut target = T::Target::default();
+        target.set_text(translated);
+        world.commands().entity(entity).insert(target);
+    }
+}
+
+/// Implements [super::I18nComponent] and the builder methods of a text component with a
+/// `translation: TextTranslation` field
+macro_rules! impl_text_translation {
+    ($component:ident, $target:ty) => {
+        impl $crate::components::text_translation::TextComponent for $component {
+            fn translation(&self) -> &$crate::components::text_translation::TextTranslation {
+                &self.translation
+            }
+        }
+
+        impl $crate::components::I18nComponent for $component {
+            type Target = $target;
+
+            fn locale(&self, i18n: &$crate::resources::I18n) -> String {
+                self.translation.locale(i18n)
+            }
+
+            fn has_fixed_locale(&self) -> bool {
+                self.translation.locale.is_some()
+            }
+
+            fn translate(&self, i18n: &$crate::resources::I18n) -> String {
+                self.translation.translate(i18n)
+            }
+
+            fn bound_args(
+                &self,
+                world: &bevy::ecs::world::World,
+                entity: bevy::ecs::entity::Entity,
+            ) -> Vec<(String, $crate::components::InterpolationType)> {
+                self.translation.bound_args(world, entity)
+            }
+
+            fn set_bound_args(
+                &mut self,
+                args: Vec<(String, $crate::components::InterpolationType)>,
+            ) {
+                for (key, value) in args {
+                    self.translation.set_arg(key, value);
+                }
+            }
+
+            #[cfg(feature = "relative_time")]
+            fn time_state(&self, now: bevy::utils::SystemTime) -> Option<u64> {
+                $crate::components::utils::args_time_state(&self.translation.args, now)
+            }
+        }
+
+        impl $component {
+            /// Set the locale for this specific translation
+            pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+                self.translation.locale = Some(locale.into());
+                self
+            }
+
+            /// Evaluate the translation as an [ICU MessageFormat](https://unicode-org.github.io/icu/userguide/format_parse/messages/)
+            /// message instead of replacing `%{name}` placeholders
+            ///
+            /// The interpolation arguments are used as the variables of the message, e.g.
+            /// `{count, plural, one {# cat} other {# cats}}` or `{gender, select, female {her} male {his} other {their}}`
+            pub fn with_message_format(mut self) -> Self {
+                self.translation.message_format = true;
+                self
+            }
+
+            /// Add a standard string interpolation argument to the translation key
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_arg(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+                self.translation.args.push((
+                    key.into(),
+                    $crate::components::InterpolationType::String(value.to_string()),
+                ));
+                self
+            }
+
+            /// Add a select interpolation argument to the translation key, its value picks the variant of
+            /// the translation, e.g. `messages.welcome.female`, falling back to `messages.welcome.other` and
+            /// then `messages.welcome`
+            ///
+            /// Select arguments are also interpolated as strings, and can be combined with each other and with
+            /// a plural `count`, `messages.welcome.female.one`
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_select_arg(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+                self.translation.args.push((
+                    key.into(),
+                    $crate::components::InterpolationType::Select(value.to_string()),
+                ));
+                self
+            }
+
+            /// Add an interpolation argument bound to a component of the entity, e.g.
+            /// `with_bound_arg("hp", |health: &Health| health.0)`
+            ///
+            /// The argument is read again every frame and the translation is updated whenever its value changes.
+            /// Strings are interpolated as they are and numbers are localized, like [Self::with_arg] and
+            /// [Self::with_num_arg]
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_bound_arg<
+                C: bevy::ecs::component::Component,
+                V: Into<$crate::components::InterpolationType>,
+            >(
+                mut self,
+                key: impl Into<String>,
+                read: impl Fn(&C) -> V + Send + Sync + 'static,
+            ) -> Self {
+                self.translation.bindings.push_component(key.into(), read);
+                self
+            }
+
+            /// Add an interpolation argument bound to a resource, e.g.
+            /// `with_resource_arg("score", |score: &Score| score.0)`
+            ///
+            /// The argument is read again every frame and the translation is updated whenever its value changes
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_resource_arg<
+                R: bevy::ecs::system::Resource,
+                V: Into<$crate::components::InterpolationType>,
+            >(
+                mut self,
+                key: impl Into<String>,
+                read: impl Fn(&R) -> V + Send + Sync + 'static,
+            ) -> Self {
+                self.translation.bindings.push_resource(key.into(), read);
+                self
+            }
+
+            /// Add an interpolation argument with the name of the key or button bound to an action in the
+            /// [super::InputPrompts], e.g. `Press %{key} to jump` with `with_input_arg("key", "jump")`
+            ///
+            /// The name is translated with the `input.key.Space` or `input.gamepad.South` keys of the locale, and
+            /// the translation is updated whenever the binding or the device in use changes. An action without
+            /// bindings is interpolated as its name
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_input_arg(self, key: impl Into<String>, action: impl Into<String>) -> Self {
+                let action = action.into();
+                self.with_resource_arg(key, move |prompts: &$crate::components::InputPrompts| {
+                    prompts.binding(&action).map_or_else(
+                        || $crate::components::InterpolationType::from(action.as_str()),
+                        Into::into,
+                    )
+                })
+            }
+
+            #[cfg(feature = "numbers")]
+            /// Add a number interpolation argument to the translation key
+            ///
+            /// A `count` argument also selects the plural form of the translation using the CLDR plural rules
+            /// of the locale, e.g. `messages.cats.one` or `messages.cats.few`, falling back to `messages.cats.other`
+            /// and then to `messages.cats`
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_num_arg(self, key: impl Into<String>, value: impl Into<f64>) -> Self {
+                self.with_num_arg_format(key, value, Default::default())
+            }
+
+            #[cfg(feature = "numbers")]
+            /// Add a number interpolation argument to the translation key, rounded, padded and displayed
+            /// with the provided [super::NumberFormat]
+            ///
+            /// A `count` argument selects the plural form of the displayed number, so `1` with 2 fraction digits
+            /// is displayed as `1.00` and uses the `other` form in English
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_num_arg_format(
+                mut self,
+                key: impl Into<String>,
+                value: impl Into<f64>,
+                format: $crate::components::NumberFormat,
+            ) -> Self {
+                self.translation.args.push((
+                    key.into(),
+                    $crate::components::InterpolationType::number(value.into(), format),
+                ));
+                self
+            }
+
+            #[cfg(feature = "datetime")]
+            /// Add a date and time interpolation argument to the translation key, formatted for the locale
+            /// with the provided [super::DateTimeFormat]
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_date_arg(
+                mut self,
+                key: impl Into<String>,
+                datetime: icu_calendar::DateTime<icu_calendar::Iso>,
+                format: $crate::components::DateTimeFormat,
+            ) -> Self {
+                self.translation.args.push((
+                    key.into(),
+                    $crate::components::InterpolationType::DateTime(
+                        $crate::components::DateTimeValue::new(datetime, format),
+                    ),
+                ));
+                self
+            }
+
+            #[cfg(feature = "currency")]
+            /// Add a currency interpolation argument to the translation key, formatted for the locale with the
+            /// symbol, placement and fraction digits of the ISO 4217 currency code
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_currency_arg(
+                mut self,
+                key: impl Into<String>,
+                amount: impl Into<f64>,
+                code: impl Into<String>,
+            ) -> Self {
+                self.translation.args.push((
+                    key.into(),
+                    $crate::components::InterpolationType::Currency(
+                        $crate::components::CurrencyValue::new(amount.into(), code),
+                    ),
+                ));
+                self
+            }
+
+            #[cfg(feature = "lists")]
+            /// Add a list interpolation argument to the translation key, the items are joined with the list
+            /// patterns of the locale, `Alice, Bob, and Carol` in `en` or `Alice, Bob und Carol` in `de`
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_list_arg(
+                self,
+                key: impl Into<String>,
+                items: impl IntoIterator<Item = impl Into<String>>,
+            ) -> Self {
+                self.with_list_arg_format(key, items, Default::default(), Default::default())
+            }
+
+            #[cfg(feature = "lists")]
+            /// Add a list interpolation argument to the translation key, joined with the provided
+            /// [super::ListStyle] and [super::ListWidth], e.g. `Alice, Bob, or Carol`
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_list_arg_format(
+                mut self,
+                key: impl Into<String>,
+                items: impl IntoIterator<Item = impl Into<String>>,
+                style: $crate::components::ListStyle,
+                width: $crate::components::ListWidth,
+            ) -> Self {
+                self.translation.args.push((
+                    key.into(),
+                    $crate::components::InterpolationType::List(
+                        $crate::components::ListValue::new(
+                            items.into_iter().map(Into::into).collect(),
+                            style,
+                            width,
+                        ),
+                    ),
+                ));
+                self
+            }
+
+            #[cfg(feature = "relative_time")]
+            /// Add a relative time interpolation argument to the translation key, e.g. `last seen %{time}` with
+            /// `4 minutes ago`, formatted for the locale with the provided [super::RelativeTimeFormat]
+            ///
+            /// The translation is updated as time elapses, whenever the displayed text changes
+            ///
+            /// This method can be called as many times as needed
+            pub fn with_relative_time_arg(
+                mut self,
+                key: impl Into<String>,
+                target: bevy::utils::SystemTime,
+                format: $crate::components::RelativeTimeFormat,
+            ) -> Self {
+                self.translation.args.push((
+                    key.into(),
+                    $crate::components::InterpolationType::RelativeTime(
+                        $crate::components::RelativeTimeValue::new(target, format),
+                    ),
+                ));
+                self
+            }
+
+            /// Replace the translation key, the text is updated on the next frame
+            pub fn set_key(&mut self, key: impl Into<String>) {
+                self.translation.key = key.into();
+            }
+
+            /// Set the value of an interpolation argument, replacing the argument with the same key if there is one
+            ///
+            /// Strings are interpolated as they are and numbers are localized, like [Self::with_arg] and
+            /// [Self::with_num_arg]. The text is updated on the next frame
+            pub fn set_arg(
+                &mut self,
+                key: impl Into<String>,
+                value: impl Into<$crate::components::InterpolationType>,
+            ) {
+                self.translation.set_arg(key.into(), value.into());
+            }
+
+            /// Remove the interpolation argument with the provided key, the text is updated on the next frame
+            pub fn remove_arg(&mut self, key: &str) {
+                self.translation.args.retain(|(k, _)| k != key);
+            }
+
+            /// Remove all of the interpolation arguments, the text is updated on the next frame
+            ///
+            /// Arguments bound to ECS data are read again on the next frame
+            pub fn clear_args(&mut self) {
+                self.translation.args.clear();
+            }
+        }
+    };
+}
+
+pub(crate) use impl_text_translation;
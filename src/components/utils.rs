@@ -1,60 +1,771 @@
-use rust_i18n::t;
+#[cfg(any(feature = "numbers", feature = "datetime", feature = "lists"))]
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
 
-use super::InterpolationType;
+use std::borrow::Cow;
+
+use bevy::ecs::{entity::Entity, world::DeferredWorld};
+
+use crate::{
+    events::MissingTranslation,
+    resources::{I18n, MissingTranslationPolicy},
+};
+
+use super::{I18nComponent, InterpolationType};
 
 #[cfg(feature = "numbers")]
 pub(super) fn f64_to_fd(value: f64) -> fixed_decimal::FixedDecimal {
     fixed_decimal::FixedDecimal::try_from_f64(value, fixed_decimal::FloatPrecision::Floating)
-        .expect(format!("Failed to parse FixedDecimal from f64: {}", value).as_str())
+        .unwrap_or_else(|_| panic!("Failed to parse FixedDecimal from f64: {}", value))
 }
 
-#[cfg(feature = "numbers")]
-pub(super) fn resolve_locale(locale: &String, label: impl ToString) -> icu_locid::Locale {
+#[cfg(any(feature = "numbers", feature = "datetime", feature = "lists"))]
+thread_local! {
+    /// ICU formatters and plural rules created so far, keyed by their type, locale and options
+    static FORMATTERS: RefCell<HashMap<(TypeId, String), Rc<dyn Any>>> = RefCell::default();
+}
+
+/// Returns the formatter of type `T` cached for the key, creating it the first time
+///
+/// Creating a formatter loads the data of its locale, which is too slow to do for every translation
+#[cfg(any(feature = "numbers", feature = "datetime", feature = "lists"))]
+pub(super) fn cached<T: 'static>(key: String, create: impl FnOnce() -> T) -> Rc<T> {
+    let id = (TypeId::of::<T>(), key);
+    if let Some(formatter) = FORMATTERS.with_borrow(|formatters| formatters.get(&id).cloned()) {
+        return formatter
+            .downcast()
+            .unwrap_or_else(|_| unreachable!("Formatters are keyed by their type"));
+    }
+    let formatter = Rc::new(create());
+    FORMATTERS.with_borrow_mut(|formatters| formatters.insert(id, formatter.clone()));
+    formatter
+}
+
+#[cfg(any(feature = "numbers", feature = "datetime", feature = "lists"))]
+pub(super) fn resolve_locale(locale: &str, label: impl ToString) -> icu_locid::Locale {
     locale
         .parse()
-        .expect(format!("Invalid locale: {} for key: {}", locale, label.to_string()).as_str())
+        .unwrap_or_else(|_| panic!("Invalid locale: {} for key: {}", locale, label.to_string()))
 }
 
 #[cfg(feature = "numbers")]
 pub(super) fn get_formatter(
-    locale: &String,
+    locale: &str,
     label: impl ToString,
-) -> icu_decimal::FixedDecimalFormatter {
-    let label_string = label.to_string();
-    let locale = resolve_locale(locale, label);
-    let locale_string = locale.to_string();
-    icu_decimal::FixedDecimalFormatter::try_new(&locale.into(), Default::default()).expect(
-        format!(
-            "Failed to create FixedDecimalFormatter for number: {} with locale: {}",
-            label_string, locale_string,
-        )
-        .as_str(),
+) -> Rc<icu_decimal::FixedDecimalFormatter> {
+    get_formatter_with_options(locale, label, Default::default())
+}
+
+#[cfg(feature = "numbers")]
+pub(super) fn get_formatter_with_options(
+    locale: &str,
+    label: impl ToString,
+    options: icu_decimal::options::FixedDecimalFormatterOptions,
+) -> Rc<icu_decimal::FixedDecimalFormatter> {
+    cached(format!("{}:{:?}", locale, options), || {
+        let label_string = label.to_string();
+        let locale = resolve_locale(locale, label);
+        let locale_string = locale.to_string();
+        icu_decimal::FixedDecimalFormatter::try_new(&locale.into(), options).unwrap_or_else(|_| {
+            panic!(
+                "Failed to create FixedDecimalFormatter for number: {} with locale: {}",
+                label_string, locale_string,
+            )
+        })
+    })
+}
+
+#[cfg(feature = "notations")]
+pub(super) fn get_compact_formatter(
+    locale: &str,
+    label: impl ToString,
+    long: bool,
+    options: icu_decimal::options::FixedDecimalFormatterOptions,
+) -> Rc<icu_experimental::compactdecimal::CompactDecimalFormatter> {
+    cached(format!("{}:{}:{:?}", locale, long, options), || {
+        let label_string = label.to_string();
+        let locale = resolve_locale(locale, label);
+        let locale_string = locale.to_string();
+        let formatter = if long {
+            icu_experimental::compactdecimal::CompactDecimalFormatter::try_new_long(
+                &locale.into(),
+                options.into(),
+            )
+        } else {
+            icu_experimental::compactdecimal::CompactDecimalFormatter::try_new_short(
+                &locale.into(),
+                options.into(),
+            )
+        };
+        formatter.unwrap_or_else(|_| {
+            panic!(
+                "Failed to create CompactDecimalFormatter for number: {} with locale: {}",
+                label_string, locale_string,
+            )
+        })
+    })
+}
+
+#[cfg(feature = "numbers")]
+pub(super) fn get_plural_rules(
+    locale: &str,
+    label: impl ToString,
+    rule_type: icu_plurals::PluralRuleType,
+) -> Rc<icu_plurals::PluralRules> {
+    cached(format!("{}:{:?}", locale, rule_type), || {
+        let label_string = label.to_string();
+        let locale = resolve_locale(locale, label);
+        let locale_string = locale.to_string();
+        icu_plurals::PluralRules::try_new(&locale.into(), rule_type).unwrap_or_else(|_| {
+            panic!(
+                "Failed to create PluralRules for key: {} with locale: {}",
+                label_string, locale_string,
+            )
+        })
+    })
+}
+
+/// Returns the date time formatter, `None` if the format isn't supported, e.g. a skeleton with a time zone
+#[cfg(feature = "datetime")]
+pub(super) fn get_datetime_formatter(
+    locale: &str,
+    format: &super::DateTimeFormat,
+    label: impl ToString,
+) -> Rc<Option<icu_datetime::DateTimeFormatter>> {
+    cached(format!("{}:{:?}", locale, format), || {
+        let label_string = label.to_string();
+        let locale = resolve_locale(locale, label);
+        let locale_string = locale.to_string();
+        icu_datetime::DateTimeFormatter::try_new_experimental(&locale.into(), format.options())
+            .map_err(|err| {
+                bevy::log::error!(
+                    "Failed to create DateTimeFormatter for date time: {} with locale: {}: {:?}",
+                    label_string,
+                    locale_string,
+                    err,
+                )
+            })
+            .ok()
+    })
+}
+
+#[cfg(feature = "relative_time")]
+pub(super) fn get_relative_time_formatter(
+    locale: &str,
+    label: impl ToString,
+    style: super::RelativeTimeStyle,
+    unit: super::i18n_relative_time::TimeUnit,
+    auto: bool,
+) -> Rc<icu_experimental::relativetime::RelativeTimeFormatter> {
+    use super::{i18n_relative_time::TimeUnit, RelativeTimeStyle};
+    use icu_experimental::relativetime::{
+        options::Numeric, RelativeTimeFormatter, RelativeTimeFormatterOptions,
+    };
+
+    cached(
+        format!("{}:{:?}:{:?}:{}", locale, style, unit, auto),
+        || {
+            let label_string = label.to_string();
+            let locale = resolve_locale(locale, label);
+            let locale_string = locale.to_string();
+            let locale = &locale.into();
+            let options = RelativeTimeFormatterOptions {
+                numeric: if auto { Numeric::Auto } else { Numeric::Always },
+            };
+            match (style, unit) {
+                (RelativeTimeStyle::Long, TimeUnit::Second) => {
+                    RelativeTimeFormatter::try_new_long_second(locale, options)
+                }
+                (RelativeTimeStyle::Long, TimeUnit::Minute) => {
+                    RelativeTimeFormatter::try_new_long_minute(locale, options)
+                }
+                (RelativeTimeStyle::Long, TimeUnit::Hour) => {
+                    RelativeTimeFormatter::try_new_long_hour(locale, options)
+                }
+                (RelativeTimeStyle::Long, TimeUnit::Day) => {
+                    RelativeTimeFormatter::try_new_long_day(locale, options)
+                }
+                (RelativeTimeStyle::Long, TimeUnit::Week) => {
+                    RelativeTimeFormatter::try_new_long_week(locale, options)
+                }
+                (RelativeTimeStyle::Long, TimeUnit::Month) => {
+                    RelativeTimeFormatter::try_new_long_month(locale, options)
+                }
+                (RelativeTimeStyle::Long, TimeUnit::Year) => {
+                    RelativeTimeFormatter::try_new_long_year(locale, options)
+                }
+                (RelativeTimeStyle::Short, TimeUnit::Second) => {
+                    RelativeTimeFormatter::try_new_short_second(locale, options)
+                }
+                (RelativeTimeStyle::Short, TimeUnit::Minute) => {
+                    RelativeTimeFormatter::try_new_short_minute(locale, options)
+                }
+                (RelativeTimeStyle::Short, TimeUnit::Hour) => {
+                    RelativeTimeFormatter::try_new_short_hour(locale, options)
+                }
+                (RelativeTimeStyle::Short, TimeUnit::Day) => {
+                    RelativeTimeFormatter::try_new_short_day(locale, options)
+                }
+                (RelativeTimeStyle::Short, TimeUnit::Week) => {
+                    RelativeTimeFormatter::try_new_short_week(locale, options)
+                }
+                (RelativeTimeStyle::Short, TimeUnit::Month) => {
+                    RelativeTimeFormatter::try_new_short_month(locale, options)
+                }
+                (RelativeTimeStyle::Short, TimeUnit::Year) => {
+                    RelativeTimeFormatter::try_new_short_year(locale, options)
+                }
+                (RelativeTimeStyle::Narrow, TimeUnit::Second) => {
+                    RelativeTimeFormatter::try_new_narrow_second(locale, options)
+                }
+                (RelativeTimeStyle::Narrow, TimeUnit::Minute) => {
+                    RelativeTimeFormatter::try_new_narrow_minute(locale, options)
+                }
+                (RelativeTimeStyle::Narrow, TimeUnit::Hour) => {
+                    RelativeTimeFormatter::try_new_narrow_hour(locale, options)
+                }
+                (RelativeTimeStyle::Narrow, TimeUnit::Day) => {
+                    RelativeTimeFormatter::try_new_narrow_day(locale, options)
+                }
+                (RelativeTimeStyle::Narrow, TimeUnit::Week) => {
+                    RelativeTimeFormatter::try_new_narrow_week(locale, options)
+                }
+                (RelativeTimeStyle::Narrow, TimeUnit::Month) => {
+                    RelativeTimeFormatter::try_new_narrow_month(locale, options)
+                }
+                (RelativeTimeStyle::Narrow, TimeUnit::Year) => {
+                    RelativeTimeFormatter::try_new_narrow_year(locale, options)
+                }
+            }
+            .unwrap_or_else(|_| {
+                panic!(
+                    "Failed to create RelativeTimeFormatter for time: {} with locale: {}",
+                    label_string, locale_string,
+                )
+            })
+        },
     )
 }
 
+#[cfg(feature = "lists")]
+pub(super) fn get_list_formatter(
+    locale: &str,
+    label: impl ToString,
+    style: super::ListStyle,
+    width: super::ListWidth,
+) -> Rc<icu_list::ListFormatter> {
+    use super::{ListStyle, ListWidth};
+    use icu_list::{ListFormatter, ListLength};
+
+    cached(format!("{}:{:?}:{:?}", locale, style, width), || {
+        let label_string = label.to_string();
+        let locale = resolve_locale(locale, label);
+        let locale_string = locale.to_string();
+        let locale = &locale.into();
+        let length = match width {
+            ListWidth::Wide => ListLength::Wide,
+            ListWidth::Short => ListLength::Short,
+            ListWidth::Narrow => ListLength::Narrow,
+        };
+        match style {
+            ListStyle::And => ListFormatter::try_new_and_with_length(locale, length),
+            ListStyle::Or => ListFormatter::try_new_or_with_length(locale, length),
+            ListStyle::Unit => ListFormatter::try_new_unit_with_length(locale, length),
+        }
+        .unwrap_or_else(|_| {
+            panic!(
+                "Failed to create ListFormatter for list: {} with locale: {}",
+                label_string, locale_string,
+            )
+        })
+    })
+}
+
+/// Returns a hash of the relative time arguments as they're displayed, `None` if there are none
+#[cfg(feature = "relative_time")]
+pub(super) fn args_time_state(
+    args: &[(String, InterpolationType)],
+    now: bevy::utils::SystemTime,
+) -> Option<u64> {
+    use std::hash::Hasher;
+
+    let mut state = std::hash::DefaultHasher::new();
+    let mut found = false;
+    for (_, value) in args {
+        if let InterpolationType::RelativeTime(value) = value {
+            value.time_state(now, &mut state);
+            found = true;
+        }
+    }
+    found.then(|| state.finish())
+}
+
+/// Name of the number interpolation argument that selects the plural form of a translation
+#[cfg(feature = "numbers")]
+pub(super) const PLURAL_ARG: &str = "count";
+
+/// Suffix of the translation key for each CLDR plural category, `messages.cats.one`
+#[cfg(feature = "numbers")]
+pub(super) fn plural_suffix(category: icu_plurals::PluralCategory) -> &'static str {
+    match category {
+        icu_plurals::PluralCategory::Zero => "zero",
+        icu_plurals::PluralCategory::One => "one",
+        icu_plurals::PluralCategory::Two => "two",
+        icu_plurals::PluralCategory::Few => "few",
+        icu_plurals::PluralCategory::Many => "many",
+        icu_plurals::PluralCategory::Other => "other",
+    }
+}
+
+/// Looks up the plural form of a key for the `count` argument, if there is one
+///
+/// Tries `{key}.{category}` first, where the category is chosen with the CLDR plural rules of the locale,
+/// and then `{key}.other`
+#[cfg(feature = "numbers")]
+fn lookup_plural(
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    args: &[(String, InterpolationType)],
+) -> Option<String> {
+    let count = args.iter().find_map(|(k, v)| match v {
+        InterpolationType::Number(count) if k == PLURAL_ARG => Some(&count.value),
+        _ => None,
+    })?;
+    let category =
+        get_plural_rules(locale, key, icu_plurals::PluralRuleType::Cardinal).category_for(count);
+
+    [plural_suffix(category), "other"]
+        .iter()
+        .find_map(|suffix| {
+            i18n.backend()
+                .translate(locale, &format!("{}.{}", key, suffix), args)
+        })
+}
+
+/// Returns the keys to try for the select arguments, the most specific first
+///
+/// Every select argument appends its value and then `other` to the keys, `messages.welcome.female`
+/// and `messages.welcome.other`, before the key without it
+fn select_keys(key: &str, args: &[(String, InterpolationType)]) -> Vec<String> {
+    args.iter()
+        .fold(vec![key.to_string()], |keys, (_, value)| match value {
+            InterpolationType::Select(value) => keys
+                .into_iter()
+                .flat_map(|key| [format!("{}.{}", key, value), format!("{}.other", key), key])
+                .collect(),
+            _ => keys,
+        })
+}
+
+/// Looks up a key with the [crate::backend::TranslationBackend] of the [I18n] resource for a single locale
+fn lookup_locale(
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    args: &[(String, InterpolationType)],
+) -> Option<String> {
+    select_keys(key, args).into_iter().find_map(|key| {
+        #[cfg(feature = "numbers")]
+        if let Some(plural) = lookup_plural(i18n, locale, &key, args) {
+            return Some(plural);
+        }
+        i18n.backend().translate(locale, &key, args)
+    })
+}
+
+/// Tries the locales of the chain in order, up to and including the resolved locale
+fn lookup_chain(
+    i18n: &I18n,
+    chain: &mut impl Iterator<Item = String>,
+    resolved: &str,
+    key: &str,
+    args: &[(String, InterpolationType)],
+) -> Option<(String, String)> {
+    for fallback in chain {
+        if let Some(text) = lookup_locale(i18n, &fallback, key, args) {
+            return Some((text, fallback));
+        }
+        if fallback == resolved {
+            break;
+        }
+    }
+    None
+}
+
+/// Looks up a translation that the app may or may not provide, e.g. to override a built-in default
+///
+/// Unlike [lookup], a missing translation isn't reported
+pub(super) fn lookup_optional(
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    args: &[(String, InterpolationType)],
+) -> Option<(String, String)> {
+    let resolved = i18n.resolve_locale(locale);
+    lookup_chain(
+        i18n,
+        &mut i18n.fallback_chain(locale).into_iter(),
+        &resolved,
+        key,
+        args,
+    )
+}
+
+/// Looks up a translation with the [crate::backend::TranslationBackend] of the [I18n] resource,
+/// trying every locale of the fallback chain in order
+///
+/// The translation is missing if the first locale of the chain that has translations doesn't have it,
+/// it is then reported and displayed according to the [crate::resources::MissingTranslationPolicy]
+///
+/// Returns the translation and the locale used to format it
+pub(super) fn lookup(
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    args: &[(String, InterpolationType)],
+) -> (String, String) {
+    let resolved = i18n.resolve_locale(locale);
+    let mut chain = i18n.fallback_chain(locale).into_iter();
+    if let Some(found) = lookup_chain(i18n, &mut chain, &resolved, key, args) {
+        return found;
+    }
+
+    // Nothing to report until the backend has translations
+    if !i18n.locales().is_empty() {
+        bevy::log::debug!(
+            "Missing translation for key: {} with locale: {}",
+            key,
+            locale
+        );
+        i18n.report_missing(key, locale);
+    }
+    let mut fallback_locale = resolved;
+    let fallback = match i18n.missing_policy() {
+        MissingTranslationPolicy::ShowFallback => chain.find_map(|fallback| {
+            let text = lookup_locale(i18n, &fallback, key, args)?;
+            fallback_locale = fallback;
+            Some(text)
+        }),
+        _ => None,
+    };
+    let text = i18n.missing_policy().display(locale, key, fallback);
+    (text, fallback_locale)
+}
+
+/// Looks up a translation and evaluates it as an ICU MessageFormat message
+pub(super) fn format_message_by_key(
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    args: &[(String, InterpolationType)],
+) -> String {
+    let (message, locale) = lookup(i18n, locale, key, args);
+    super::message_format::format_message(&locale, &message, &localize_inputs(i18n, &locale, args))
+}
+
+/// Replaces the `%{name}` placeholders of a translation with the provided values
+///
+/// Unknown placeholders are left untouched
+fn replace_patterns(input: &str, values: &[(&str, String)]) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("%{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &after[..end];
+        match values.iter().find(|(k, _)| *k == name) {
+            Some((_, value)) => output.push_str(value),
+            None => output.push_str(&rest[start..start + end + 3]),
+        }
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
 pub(super) fn translate_by_key(
-    locale: &String,
-    key: &String,
-    args: &Vec<(String, InterpolationType)>,
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    args: &[(String, InterpolationType)],
 ) -> String {
-    let key = key.as_str();
+    let (translated, locale) = lookup(i18n, locale, key, args);
+    interpolate(&translated, &locale, &localize_inputs(i18n, &locale, args))
+}
 
-    #[cfg(feature = "numbers")]
-    let fdf = super::utils::get_formatter(locale, key);
+/// Replaces the input arguments with the name of their key or button translated for the locale
+fn localize_inputs<'a>(
+    i18n: &I18n,
+    locale: &str,
+    args: &'a [(String, InterpolationType)],
+) -> Cow<'a, [(String, InterpolationType)]> {
+    if !args
+        .iter()
+        .any(|(_, value)| matches!(value, InterpolationType::Input(_)))
+    {
+        return Cow::Borrowed(args);
+    }
+    args.iter()
+        .map(|(key, value)| match value {
+            InterpolationType::Input(binding) => (
+                key.clone(),
+                InterpolationType::String(binding.format_to_string(i18n, locale)),
+            ),
+            _ => (key.clone(), value.clone()),
+        })
+        .collect()
+}
+
+/// Replaces the `%{name}` placeholders of a translation with the arguments formatted for the locale
+pub(super) fn interpolate(
+    translated: &str,
+    locale: &str,
+    args: &[(String, InterpolationType)],
+) -> String {
+    #[cfg(not(any(feature = "numbers", feature = "datetime", feature = "lists")))]
+    let _ = locale;
 
-    let (patterns, values): (Vec<&str>, Vec<String>) = args
+    let values: Vec<(&str, String)> = args
         .iter()
         .map(|(k, interpolation_type)| {
             let value = match interpolation_type {
-                InterpolationType::String(v) => v.clone(),
+                InterpolationType::String(v) | InterpolationType::Select(v) => v.clone(),
                 #[cfg(feature = "numbers")]
-                InterpolationType::Number(v) => fdf.format_to_string(v),
+                InterpolationType::Number(v) => v.format_to_string(locale),
+                #[cfg(feature = "datetime")]
+                InterpolationType::DateTime(v) => v.format_to_string(locale),
+                #[cfg(feature = "currency")]
+                InterpolationType::Currency(v) => v.format_to_string(locale),
+                #[cfg(feature = "relative_time")]
+                InterpolationType::RelativeTime(v) => v.format_to_string(locale),
+                #[cfg(feature = "lists")]
+                InterpolationType::List(v) => v.format_to_string(locale),
+                InterpolationType::Input(v) => v.to_string(),
             };
             (k.as_str(), value)
         })
-        .unzip();
-    let translated = t!(key, locale = locale);
+        .collect();
+
+    replace_patterns(translated, &values)
+}
+
+/// Translates a component from its hook and sends a [MissingTranslation] event for every missing key
+pub(super) fn translate_entity<T: I18nComponent>(
+    world: &mut DeferredWorld,
+    entity: Entity,
+    component: &T,
+) -> String {
+    let i18n = world
+        .get_resource::<I18n>()
+        .expect("I18n resource has not been initialized");
+    let translated = component.translate(i18n);
+    let missing = i18n.take_missing();
+    for (key, locale) in missing {
+        world.send_event(MissingTranslation {
+            entity,
+            key,
+            locale,
+        });
+    }
+    translated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assets::LocaleFile, backend::LocaleFileBackend};
+
+    fn i18n(locale: &str, json: &str) -> I18n {
+        let mut backend = LocaleFileBackend::default();
+        backend.extend(&LocaleFile::parse(json, "json", locale).unwrap());
+        let mut i18n = I18n::new(backend);
+        i18n.set_locale(locale);
+        i18n
+    }
+
+    fn fr_with_en_fallback() -> I18n {
+        let mut backend = LocaleFileBackend::default();
+        for (locale, json) in [
+            ("en", r#"{ "hello": "Hello", "bye": "Bye" }"#),
+            ("fr", r#"{ "hello": "Bonjour" }"#),
+        ] {
+            backend.extend(&LocaleFile::parse(json, "json", locale).unwrap());
+        }
+        let mut i18n = I18n::new(backend);
+        i18n.set_locale("fr");
+        i18n.set_fallbacks("fr", ["en"]);
+        i18n
+    }
+
+    fn values(values: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+        values.iter().map(|(k, v)| (*k, v.to_string())).collect()
+    }
+
+    #[test]
+    fn placeholders_are_replaced() {
+        let values = values(&[("name", "Bevy"), ("n", "3")]);
+        assert_eq!(
+            replace_patterns("Hello, %{name}! %{n}%{n}", &values),
+            "Hello, Bevy! 33"
+        );
+        assert_eq!(
+            replace_patterns("こんにちは %{name}", &values),
+            "こんにちは Bevy"
+        );
+        assert_eq!(
+            replace_patterns("No placeholders", &values),
+            "No placeholders"
+        );
+    }
+
+    #[test]
+    fn unknown_and_unclosed_placeholders_are_untouched() {
+        let values = values(&[("name", "Bevy")]);
+        assert_eq!(
+            replace_patterns("%{other} and %{name}", &values),
+            "%{other} and Bevy"
+        );
+        assert_eq!(replace_patterns("%{name} %{name", &values), "Bevy %{name");
+        assert_eq!(replace_patterns("100% {name}", &values), "100% {name}");
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn count_selects_the_plural_form() {
+        let i18n = i18n(
+            "en",
+            r#"{ "cats": { "one": "%{count} cat", "other": "%{count} cats" }, "dogs": { "other": "%{count} dogs" }, "birds": "%{count} birds" }"#,
+        );
+        let translate = |key: &str, count: i32| {
+            translate_by_key(&i18n, "en", key, &[("count".to_string(), count.into())])
+        };
+        assert_eq!(translate("cats", 1), "1 cat");
+        assert_eq!(translate("cats", 0), "0 cats");
+        assert_eq!(translate("cats", 1000), "1,000 cats");
+        assert_eq!(translate("dogs", 1), "1 dogs");
+        assert_eq!(translate("birds", 1), "1 birds");
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn plural_rules_of_the_locale_are_used() {
+        let i18n = i18n(
+            "pl",
+            r#"{ "cats": { "one": "%{count} kot", "few": "%{count} koty", "many": "%{count} kotów", "other": "%{count} kota" } }"#,
+        );
+        let translate = |count: f64| {
+            translate_by_key(&i18n, "pl", "cats", &[("count".to_string(), count.into())])
+        };
+        assert_eq!(translate(1.), "1 kot");
+        assert_eq!(translate(3.), "3 koty");
+        assert_eq!(translate(5.), "5 kotów");
+        assert_eq!(translate(1.5), "1,5 kota");
+    }
+
+    fn select(key: &str, value: &str) -> (String, InterpolationType) {
+        (
+            key.to_string(),
+            InterpolationType::Select(value.to_string()),
+        )
+    }
+
+    #[test]
+    fn select_keys_are_tried_from_the_most_specific() {
+        assert_eq!(select_keys("welcome", &[]), ["welcome"]);
+        assert_eq!(
+            select_keys("welcome", &[select("gender", "female")]),
+            ["welcome.female", "welcome.other", "welcome"]
+        );
+        assert_eq!(
+            select_keys(
+                "welcome",
+                &[
+                    select("gender", "female"),
+                    ("name".to_string(), "Ann".into()),
+                    select("role", "admin"),
+                ]
+            ),
+            [
+                "welcome.female.admin",
+                "welcome.female.other",
+                "welcome.female",
+                "welcome.other.admin",
+                "welcome.other.other",
+                "welcome.other",
+                "welcome.admin",
+                "welcome.other",
+                "welcome",
+            ]
+        );
+    }
+
+    #[test]
+    fn select_arguments_pick_the_variant() {
+        let i18n = i18n(
+            "en",
+            r#"{ "welcome": { "female": "Welcome, %{gender} %{name}", "other": "Welcome, %{name}" }, "bye": "Bye" }"#,
+        );
+        let translate = |key: &str, gender: &str| {
+            translate_by_key(
+                &i18n,
+                "en",
+                key,
+                &[select("gender", gender), ("name".to_string(), "Ann".into())],
+            )
+        };
+        assert_eq!(translate("welcome", "female"), "Welcome, female Ann");
+        assert_eq!(translate("welcome", "male"), "Welcome, Ann");
+        assert_eq!(translate("bye", "female"), "Bye");
+    }
+
+    #[test]
+    fn missing_translations_follow_the_policy() {
+        let mut i18n = fr_with_en_fallback();
+        assert_eq!(translate_by_key(&i18n, "fr", "hello", &[]), "Bonjour");
+        assert_eq!(translate_by_key(&i18n, "fr", "bye", &[]), "Bye");
+        assert_eq!(translate_by_key(&i18n, "fr", "unknown", &[]), "fr.unknown");
+
+        i18n.set_missing_policy(MissingTranslationPolicy::ShowKey);
+        assert_eq!(translate_by_key(&i18n, "fr", "bye", &[]), "fr.bye");
+
+        i18n.set_missing_policy(MissingTranslationPolicy::Placeholder);
+        assert_eq!(
+            translate_by_key(&i18n, "fr", "bye", &[]),
+            "[MISSING fr: bye]"
+        );
+    }
+
+    #[test]
+    fn missing_translations_are_recorded_once_per_key_and_locale() {
+        let i18n = fr_with_en_fallback();
+        assert!(i18n.take_missing().is_empty());
+
+        translate_by_key(&i18n, "fr", "hello", &[]);
+        assert!(i18n.take_missing().is_empty());
 
-    let val = rust_i18n::replace_patterns(&translated, patterns.as_slice(), values.as_slice());
-    val
+        translate_by_key(&i18n, "fr", "bye", &[]);
+        translate_by_key(
+            &i18n,
+            "fr",
+            "unknown",
+            &[select("gender", "female"), select("role", "admin")],
+        );
+        assert_eq!(
+            i18n.take_missing(),
+            [
+                ("bye".to_string(), "fr".to_string()),
+                ("unknown".to_string(), "fr".to_string())
+            ]
+        );
+        assert!(i18n.take_missing().is_empty());
+    }
 }
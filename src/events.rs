@@ -0,0 +1,40 @@
+use bevy::{
+    diagnostic::DiagnosticPath,
+    ecs::{entity::Entity, event::Event},
+    reflect::Reflect,
+};
+
+/// Prefix of the per-locale missing translation diagnostics, `i18n/missing_translations/ja`
+pub const MISSING_TRANSLATIONS_DIAGNOSTIC: &str = "i18n/missing_translations";
+
+/// Returns the path of the diagnostic counting the missing translation keys of a locale
+pub fn missing_translations_diagnostic(locale: &str) -> DiagnosticPath {
+    DiagnosticPath::from_components([MISSING_TRANSLATIONS_DIAGNOSTIC, locale])
+}
+
+/// Event sent whenever an entity is translated with a key that's missing for its locale
+///
+/// A key is missing when the first locale of its fallback chain that has translations doesn't have it,
+/// what's displayed instead depends on the [crate::resources::MissingTranslationPolicy]
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_simple_i18n::prelude::*;
+///
+/// fn report_missing(mut events: EventReader<MissingTranslation>) {
+///     for event in events.read() {
+///         warn!("{:?} is missing {} for {} locale", event.entity, event.key, event.locale);
+///     }
+/// }
+/// ```
+#[derive(Event, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct MissingTranslation {
+    /// Entity that was translated
+    pub entity: Entity,
+    /// Missing translation key
+    pub key: String,
+    /// Locale of the entity
+    pub locale: String,
+}
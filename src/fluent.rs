@@ -0,0 +1,255 @@
+use bevy::utils::hashbrown::{HashMap, HashSet};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use fluent_syntax::ast;
+use unic_langid::LanguageIdentifier;
+
+use crate::components::InterpolationType;
+
+/// Fluent bundles built from every loaded `.ftl` locale file, one per locale
+#[derive(Default)]
+pub(crate) struct FluentCatalog {
+    bundles: HashMap<String, FluentBundle<FluentResource>>,
+    /// Keys of the messages and attributes of every bundle, `menu` and `menu.title`
+    keys: HashMap<String, HashSet<String>>,
+}
+
+impl FluentCatalog {
+    /// Adds the messages of a Fluent resource to the bundle of its locale, overriding existing messages
+    pub(crate) fn extend(&mut self, locale: &str, source: &str) {
+        let langid = match locale.parse::<LanguageIdentifier>() {
+            Ok(langid) => langid,
+            Err(err) => {
+                bevy::log::error!("Invalid Fluent locale {}: {}", locale, err);
+                return;
+            }
+        };
+        let resource =
+            FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+                for err in errors {
+                    bevy::log::error!("Fluent syntax error for {} locale: {}", locale, err);
+                }
+                resource
+            });
+
+        let keys = self.keys.entry(locale.to_string()).or_default();
+        for entry in resource.entries() {
+            let ast::Entry::Message(message) = entry else {
+                continue;
+            };
+            let id = message.id.name;
+            if message.value.is_some() {
+                keys.insert(id.to_string());
+            }
+            for attribute in message.attributes.iter() {
+                keys.insert(format!("{}.{}", id, attribute.id.name));
+            }
+        }
+
+        self.bundles
+            .entry(locale.to_string())
+            .or_insert_with(|| {
+                let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+                // Bevy fonts render the Unicode isolation marks as unknown glyphs
+                bundle.set_use_isolating(false);
+                #[cfg(feature = "numbers")]
+                bundle.set_formatter(Some(format_number));
+                bundle
+            })
+            .add_resource_overriding(resource);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.bundles.clear();
+        self.keys.clear();
+    }
+
+    pub(crate) fn locales(&self) -> impl Iterator<Item = &String> {
+        self.bundles.keys()
+    }
+
+    /// Returns the keys of the messages and attributes of the locale, in the format used by [Self::format]
+    pub(crate) fn keys(&self, locale: &str) -> impl Iterator<Item = &String> {
+        self.keys.get(locale).into_iter().flatten()
+    }
+
+    /// Formats the message for the provided locale and key, using the interpolation arguments as Fluent variables
+    ///
+    /// Keys with a `.` refer to the attribute of a message, `menu.title` is the `title` attribute
+    /// of the `menu` message
+    pub(crate) fn format(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &[(String, InterpolationType)],
+    ) -> Option<String> {
+        let bundle = self.bundles.get(locale)?;
+        let mut fluent_args = FluentArgs::with_capacity(args.len());
+        for (k, v) in args {
+            fluent_args.set(k.as_str(), fluent_value(locale, v));
+        }
+        format_message(bundle, key, &fluent_args)
+    }
+}
+
+/// Converts an interpolation argument to a Fluent variable
+///
+/// Numbers stay numbers, so they can be used in Fluent plural selectors, and the other values are formatted
+/// for the locale
+fn fluent_value(locale: &str, value: &InterpolationType) -> FluentValue<'static> {
+    #[cfg(not(any(
+        feature = "datetime",
+        feature = "currency",
+        feature = "relative_time",
+        feature = "lists"
+    )))]
+    let _ = locale;
+
+    match value {
+        InterpolationType::String(v) | InterpolationType::Select(v) => {
+            FluentValue::String(v.clone().into())
+        }
+        #[cfg(feature = "numbers")]
+        InterpolationType::Number(v) => match v.to_fluent() {
+            Some(number) => FluentValue::Number(number),
+            None => FluentValue::String(v.format_to_string(locale).into()),
+        },
+        #[cfg(feature = "datetime")]
+        InterpolationType::DateTime(v) => FluentValue::String(v.format_to_string(locale).into()),
+        #[cfg(feature = "currency")]
+        InterpolationType::Currency(v) => FluentValue::String(v.format_to_string(locale).into()),
+        #[cfg(feature = "relative_time")]
+        InterpolationType::RelativeTime(v) => {
+            FluentValue::String(v.format_to_string(locale).into())
+        }
+        #[cfg(feature = "lists")]
+        InterpolationType::List(v) => FluentValue::String(v.format_to_string(locale).into()),
+        InterpolationType::Input(v) => FluentValue::String(v.to_string().into()),
+    }
+}
+
+/// Locale of a Fluent bundle, read by [format_number]
+#[cfg(feature = "numbers")]
+struct BundleLocale(String);
+
+#[cfg(feature = "numbers")]
+impl intl_memoizer::Memoizable for BundleLocale {
+    type Args = ();
+    type Error = std::convert::Infallible;
+
+    fn construct(lang: LanguageIdentifier, _: ()) -> Result<Self, Self::Error> {
+        Ok(Self(lang.to_string()))
+    }
+}
+
+/// Formats the Fluent numbers with the ICU formatter of the locale of the bundle, Fluent itself doesn't
+/// localize them
+#[cfg(feature = "numbers")]
+fn format_number(
+    value: &FluentValue,
+    intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+) -> Option<String> {
+    let FluentValue::Number(number) = value else {
+        return None;
+    };
+    let locale = intls
+        .with_try_get::<BundleLocale, _, _>((), |locale| locale.0.clone())
+        .ok()?;
+    Some(crate::components::NumberValue::from_fluent(number)?.format_to_string(&locale))
+}
+
+fn format_message(
+    bundle: &FluentBundle<FluentResource>,
+    key: &str,
+    args: &FluentArgs,
+) -> Option<String> {
+    let pattern = match bundle.get_message(key) {
+        Some(message) => message.value()?,
+        None => {
+            let (id, attribute) = key.split_once('.')?;
+            bundle.get_message(id)?.get_attribute(attribute)?.value()
+        }
+    };
+
+    let mut errors = vec![];
+    let text = bundle.format_pattern(pattern, Some(args), &mut errors);
+    for err in errors {
+        // `validate_locales` formats the messages without arguments, their variables are missing on purpose
+        if args.iter().next().is_none() {
+            bevy::log::debug!("Fluent error for key {}: {}", key, err);
+        } else {
+            bevy::log::warn!("Fluent error for key {}: {}", key, err);
+        }
+    }
+    Some(text.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(locale: &str) -> FluentCatalog {
+        let mut catalog = FluentCatalog::default();
+        catalog.extend(
+            locale,
+            "code = Code {$code}\nitems = {$count ->\n    [one] {$count} item\n   *[other] {$count} items\n}\n",
+        );
+        catalog
+    }
+
+    fn format(locale: &str, key: &str, arg: (&str, InterpolationType)) -> Option<String> {
+        catalog(locale).format(locale, key, &[(arg.0.to_string(), arg.1)])
+    }
+
+    #[test]
+    fn strings_are_not_parsed_as_numbers() {
+        for code in ["007", "1e3", "Infinity"] {
+            assert_eq!(
+                format("en", "code", ("code", code.into())),
+                Some(format!("Code {}", code))
+            );
+        }
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn numbers_are_localized_and_select_plurals() {
+        use crate::components::{NumberFormat, NumberValue};
+
+        assert_eq!(
+            format("en", "items", ("count", 1.into())),
+            Some("1 item".to_string())
+        );
+        assert_eq!(
+            format("en", "items", ("count", 1234.5.into())),
+            Some("1,234.5 items".to_string())
+        );
+        assert_eq!(
+            format("de", "items", ("count", 1234.5.into())),
+            Some("1.234,5 items".to_string())
+        );
+        let value = NumberValue::new(
+            &fixed_decimal::FixedDecimal::from(3),
+            NumberFormat::new().with_fraction_digits(2),
+        );
+        assert_eq!(
+            format("en", "items", ("count", InterpolationType::Number(value))),
+            Some("3.00 items".to_string())
+        );
+    }
+
+    #[cfg(feature = "lists")]
+    #[test]
+    fn other_values_are_formatted_for_the_locale() {
+        use crate::components::{ListStyle, ListValue, ListWidth};
+
+        let list = ListValue::new(
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            ListStyle::And,
+            ListWidth::Wide,
+        );
+        assert_eq!(
+            format("en", "code", ("code", InterpolationType::List(list))),
+            Some("Code a, b, and c".to_string())
+        );
+    }
+}
@@ -1,11 +1,21 @@
+mod assets;
+mod backend;
 mod components;
+mod events;
+#[cfg(feature = "fluent")]
+mod fluent;
 mod plugin;
 mod resources;
+mod validation;
 
 include!(concat!(env!("OUT_DIR"), "/bevy_simple_i18n.rs"));
 
 pub mod prelude {
+    pub use crate::assets::{LocaleFile, LocaleFileError, LocaleFileLoader};
+    pub use crate::backend::{LocaleFileBackend, TranslationBackend};
     pub use crate::components::*;
+    pub use crate::events::*;
     pub use crate::plugin::*;
     pub use crate::resources::*;
+    pub use crate::validation::*;
 }
@@ -2,35 +2,52 @@ use std::path::Path;
 
 use bevy::{
     app::{App, Plugin, PreStartup, Update},
-    asset::{AssetServer, Handle},
+    asset::{AssetApp, AssetEvent, AssetServer, Assets, Handle},
+    diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticsStore},
     ecs::{
+        change_detection::{DetectChanges, Ref},
         component::Component,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        query::{Changed, QueryData},
         schedule::{
-            common_conditions::{resource_changed, resource_exists, resource_removed},
-            IntoSystemConfigs,
+            common_conditions::{
+                any_with_component, on_event, resource_changed, resource_exists, resource_removed,
+            },
+            IntoSystemConfigs, SystemSet,
         },
-        system::{Commands, Query, Res, ResMut},
+        system::{Commands, Local, Query, Res, ResMut},
     },
     text::{Font, TextFont},
-    ui::widget::Text,
+    utils::Instant,
 };
 
 use crate::{
-    components::{I18nFont, I18nNumber, I18nText},
-    prelude::{I18nComponent, I18nText2d},
-    resources::{FontFolder, FontManager, FontsLoading, I18n},
-    FONT_FAMILIES,
+    assets::{LocaleFile, LocaleFileLoader},
+    components::{
+        apply_bound_args, read_bound_args, update_input_device, update_rich_text, BoundArgUpdates,
+        HasBoundArgs, I18nFont, I18nNumber, I18nText, InputPrompts, RichTextMarkup, RichTextStyles,
+    },
+    events::{missing_translations_diagnostic, MissingTranslation},
+    prelude::{I18nComponent, I18nText2d, I18nTextSpan, TranslationTarget},
+    resources::{
+        FontFolder, FontManager, FontsLoading, I18n, I18nComponents, LocaleManager,
+        MissingTranslations,
+    },
+    validation::{validate_locales, LocaleValidation},
+    FONT_FAMILIES, LOCALE_FILES,
 };
 
 /// Initializes the `bevy_simple_i18n` plugin
 ///
 /// # Example
-/// ```
+/// ```no_run
 /// use bevy::prelude::*;
 /// use bevy_simple_i18n::prelude::*;
 ///
 /// fn main() {
 ///     App::new()
+///         .add_plugins(DefaultPlugins)
 ///         .add_plugins(I18nPlugin)
 ///         .run();
 /// }
@@ -39,20 +56,46 @@ pub struct I18nPlugin;
 
 impl Plugin for I18nPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.init_resource::<I18n>()
+        app.init_asset::<LocaleFile>()
+            .init_asset_loader::<LocaleFileLoader>()
+            .init_resource::<I18n>()
             .init_resource::<FontManager>()
             .init_resource::<FontsLoading>()
-            .add_systems(PreStartup, load_dynamic_fonts)
-            .register_i18n_component::<I18nText>()
-            .register_i18n_component::<I18nText2d>()
-            .register_i18n_component::<I18nNumber>()
+            .init_resource::<LocaleManager>()
+            .init_resource::<MissingTranslations>()
+            .init_resource::<RichTextStyles>()
+            .init_resource::<InputPrompts>()
+            .add_event::<MissingTranslation>()
+            .add_systems(PreStartup, (load_locale_files, load_dynamic_fonts))
             .add_systems(
                 Update,
-                monitor_font_loading.run_if(resource_exists::<FontsLoading>),
-            );
+                (
+                    update_translation_backend.before(TranslationSystems),
+                    count_missing_translations.run_if(on_event::<MissingTranslation>),
+                    monitor_font_loading.run_if(resource_exists::<FontsLoading>),
+                    update_rich_text.after(TranslationSystems),
+                    update_input_device.before(TranslationSystems),
+                ),
+            )
+            .register_i18n_component::<I18nText>()
+            .register_i18n_component::<I18nText2d>()
+            .register_i18n_component::<I18nTextSpan>()
+            .register_i18n_component::<I18nNumber>();
+        #[cfg(feature = "datetime")]
+        app.register_i18n_component::<crate::components::I18nDateTime>();
+        #[cfg(feature = "currency")]
+        app.register_i18n_component::<crate::components::I18nCurrency>();
+        #[cfg(feature = "units")]
+        app.register_i18n_component::<crate::components::I18nMeasure>();
+        #[cfg(feature = "relative_time")]
+        app.register_i18n_component::<crate::components::I18nRelativeTime>();
     }
 }
 
+/// Systems that update the translations of the registered components
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+struct TranslationSystems;
+
 pub trait I18nComponentRegistration {
     /// Registers an i18n component for automatic translation updates
     fn register_i18n_component<T: I18nComponent + Component>(&mut self) -> &mut Self;
@@ -60,12 +103,32 @@ pub trait I18nComponentRegistration {
 
 impl I18nComponentRegistration for App {
     fn register_i18n_component<T: I18nComponent + Component>(&mut self) -> &mut Self {
+        self.init_resource::<I18nComponents>()
+            .init_resource::<BoundArgUpdates<T>>()
+            .world_mut()
+            .resource_mut::<I18nComponents>()
+            .register::<T>();
         self.add_systems(
             Update,
             (
                 update_text_translations::<T>.run_if(resource_removed::<FontsLoading>),
                 update_text_translations::<T>.run_if(resource_changed::<I18n>),
-            ),
+                #[cfg(feature = "relative_time")]
+                (
+                    mark_relative_times::<T>,
+                    update_elapsed_translations::<T>
+                        .run_if(any_with_component::<crate::components::HasRelativeTime>),
+                )
+                    .chain(),
+                (
+                    (read_bound_args::<T>, apply_bound_args::<T>)
+                        .chain()
+                        .run_if(any_with_component::<HasBoundArgs>),
+                    update_changed_translations::<T>,
+                )
+                    .chain(),
+            )
+                .in_set(TranslationSystems),
         )
     }
 }
@@ -73,15 +136,252 @@ impl I18nComponentRegistration for App {
 /// Auto updates the translations for components that have the [I18nComponent] trait
 /// and have been registered with the Bevy [App] using the [register_i18n_component] method
 /// whenever the [I18n] resource changes
+///
+/// Components with a fixed locale are skipped when only the current locale changed, and the text and
+/// font are only replaced when they're different, so unchanged entities aren't laid out again
 fn update_text_translations<T: I18nComponent + Component>(
-    font_manager: bevy::ecs::system::Res<FontManager>,
-    mut text_query: Query<(&mut Text, &mut TextFont, Option<&I18nFont>, &T)>,
+    i18n: Res<I18n>,
+    font_manager: Res<FontManager>,
+    mut revision: Local<Option<u64>>,
+    mut missing_events: EventWriter<MissingTranslation>,
+    mut text_query: Query<(Entity, TranslatedText<T>, TranslatedFont, &T)>,
 ) {
     bevy::log::debug!("Updating translations");
-    for (mut text, mut text_font, dyn_font, key) in text_query.iter_mut() {
-        text.0 = key.translate();
-        if let Some(dyn_font) = dyn_font {
-            text_font.font = font_manager.get(&dyn_font.0, key.locale());
+    let locale_changed = *revision == Some(i18n.revision()) && !font_manager.is_changed();
+    *revision = Some(i18n.revision());
+    for (entity, mut text, (mut text_font, dyn_font), key) in text_query.iter_mut() {
+        if locale_changed && key.has_fixed_locale() {
+            continue;
+        }
+        text.set(key.translate(&i18n));
+        missing_events.send_batch(i18n.take_missing().into_iter().map(|(key, locale)| {
+            MissingTranslation {
+                entity,
+                key,
+                locale,
+            }
+        }));
+        if let (Some(dyn_font), Some(text_font)) = (dyn_font, text_font.as_mut()) {
+            let font = font_manager.get(&dyn_font.0, &i18n.fallback_chain(&key.locale(&i18n)));
+            if text_font.font != font {
+                text_font.font = font;
+            }
+        }
+    }
+}
+
+/// Font of a translated entity, and its dynamic font if it has one
+type TranslatedFont = (Option<&'static mut TextFont>, Option<&'static I18nFont>);
+
+/// [TranslationTarget] of a translated entity, or its markup if it's displayed as rich text
+#[derive(QueryData)]
+#[query_data(mutable)]
+struct TranslatedText<T: I18nComponent + Component> {
+    target: &'static mut T::Target,
+    markup: Option<&'static mut RichTextMarkup>,
+}
+
+impl<T: I18nComponent + Component> TranslatedTextItem<'_, T> {
+    /// Replaces the text, or the rich text markup, only if the translation is different, to keep the change
+    /// detection of the target accurate
+    fn set(&mut self, translated: String) {
+        match self.markup.as_mut() {
+            Some(markup) if markup.0 != translated => markup.0 = translated,
+            Some(_) => {}
+            None if self.target.text() != translated => self.target.set_text(translated),
+            None => {}
+        }
+    }
+}
+
+/// Added or changed components that aren't marked with [crate::components::HasRelativeTime] yet
+#[cfg(feature = "relative_time")]
+type NotMarkedRelativeTime<T> = (
+    Changed<T>,
+    bevy::ecs::query::Without<crate::components::HasRelativeTime>,
+);
+
+/// Marks the added or changed components whose text changes as time elapses with
+/// [crate::components::HasRelativeTime], e.g. [crate::components::I18nRelativeTime] or texts with a
+/// relative time argument
+#[cfg(feature = "relative_time")]
+fn mark_relative_times<T: I18nComponent + Component>(
+    mut commands: Commands,
+    query: Query<(Entity, &T), NotMarkedRelativeTime<T>>,
+) {
+    let now = bevy::utils::SystemTime::now();
+    for (entity, component) in query.iter() {
+        if component.time_state(now).is_some() {
+            commands
+                .entity(entity)
+                .insert(crate::components::HasRelativeTime);
+        }
+    }
+}
+
+/// Updates the translations of the components whose text changes as time elapses, e.g. `3 minutes ago`,
+/// whenever their [I18nComponent::time_state] changes
+#[cfg(feature = "relative_time")]
+fn update_elapsed_translations<T: I18nComponent + Component>(
+    i18n: Res<I18n>,
+    mut states: Local<bevy::utils::HashMap<Entity, u64>>,
+    mut missing_events: EventWriter<MissingTranslation>,
+    mut text_query: Query<
+        (Entity, TranslatedText<T>, &T),
+        bevy::ecs::query::With<crate::components::HasRelativeTime>,
+    >,
+) {
+    let now = bevy::utils::SystemTime::now();
+    let previous = std::mem::take(&mut *states);
+    for (entity, mut text, component) in text_query.iter_mut() {
+        let Some(state) = component.time_state(now) else {
+            continue;
+        };
+        states.insert(entity, state);
+        if previous.get(&entity) == Some(&state) {
+            continue;
+        }
+        text.set(component.translate(&i18n));
+        missing_events.send_batch(i18n.take_missing().into_iter().map(|(key, locale)| {
+            MissingTranslation {
+                entity,
+                key,
+                locale,
+            }
+        }));
+    }
+}
+
+/// Updates the translations of the components that changed after they were added, e.g. with
+/// [I18nText::set_key] or [I18nText::set_arg]
+fn update_changed_translations<T: I18nComponent + Component>(
+    i18n: Res<I18n>,
+    mut missing_events: EventWriter<MissingTranslation>,
+    mut text_query: Query<(Entity, TranslatedText<T>, Ref<T>), Changed<T>>,
+) {
+    for (entity, mut text, component) in text_query.iter_mut() {
+        // Added components are translated by their hook
+        if component.is_added() {
+            continue;
+        }
+        text.set(component.translate(&i18n));
+        missing_events.send_batch(i18n.take_missing().into_iter().map(|(key, locale)| {
+            MissingTranslation {
+                entity,
+                key,
+                locale,
+            }
+        }));
+    }
+}
+
+/// Loads the locale files specified in the [LOCALE_FILES] constant that's generated by the build script
+fn load_locale_files(mut locale_manager: ResMut<LocaleManager>, asset_server: Res<AssetServer>) {
+    for path in LOCALE_FILES.iter() {
+        bevy::log::debug!("Loading locale file: {}", path);
+        locale_manager.files.push(asset_server.load(*path));
+    }
+}
+
+/// Passes every loaded locale file to the [crate::backend::TranslationBackend] whenever a [LocaleFile] is added, modified or removed,
+/// or when the backend has been replaced with [I18n::set_backend]
+///
+/// Files found by the build script are merged first, so locale files loaded by the app
+/// (patches, DLC languages, etc.) override their keys
+///
+/// With the `hot_reload` feature enabled, edits to the locale files are picked up by Bevy's
+/// file watcher and end up here as [AssetEvent::Modified] events
+fn update_translation_backend(
+    mut events: EventReader<AssetEvent<LocaleFile>>,
+    locale_files: Res<Assets<LocaleFile>>,
+    locale_manager: Res<LocaleManager>,
+    validation: Option<Res<LocaleValidation>>,
+    mut i18n: ResMut<I18n>,
+) {
+    let changed = events
+        .read()
+        .filter(|event| {
+            matches!(
+                event,
+                AssetEvent::Added { .. } | AssetEvent::Modified { .. } | AssetEvent::Removed { .. }
+            )
+        })
+        .count();
+    if changed == 0 && !i18n.backend_replaced() {
+        return;
+    }
+
+    let mut files: Vec<&LocaleFile> = locale_manager
+        .files
+        .iter()
+        .filter_map(|handle| locale_files.get(handle))
+        .collect();
+    files.extend(
+        locale_files
+            .iter()
+            .filter(|(id, _)| !locale_manager.files.iter().any(|handle| handle.id() == *id))
+            .map(|(_, file)| file),
+    );
+    // Also marks the resource as changed, which re-translates every registered component
+    i18n.load_locale_files(&files);
+    bevy::log::debug!("Translation backend updated");
+
+    // Locale files are loaded one at a time, the locales that aren't loaded yet would be reported as missing
+    // every key
+    if let Some(validation) = validation.filter(|_| all_loaded(&locale_manager, &locale_files)) {
+        for report in validate_locales(i18n.backend(), &validation.base_locale) {
+            if !report.is_ok() {
+                bevy::log::warn!("{}", report);
+            }
+        }
+    }
+}
+
+/// Returns `true` once every locale file found by the build script has been loaded
+fn all_loaded(locale_manager: &LocaleManager, locale_files: &Assets<LocaleFile>) -> bool {
+    locale_manager
+        .files
+        .iter()
+        .all(|handle| locale_files.contains(handle))
+}
+
+/// Counts the distinct missing keys of every locale from the [MissingTranslation] events
+///
+/// The counts are also exposed as Bevy diagnostics if the `DiagnosticsPlugin` has been added
+fn count_missing_translations(
+    mut events: EventReader<MissingTranslation>,
+    mut missing: ResMut<MissingTranslations>,
+    diagnostics: Option<ResMut<DiagnosticsStore>>,
+) {
+    let mut changed = vec![];
+    for event in events.read() {
+        let keys = missing.keys.entry(event.locale.clone()).or_default();
+        if !keys.insert(event.key.clone()) {
+            continue;
+        }
+        bevy::log::warn!(
+            "Missing translation for key: {} with locale: {}",
+            event.key,
+            event.locale
+        );
+        if !changed.contains(&event.locale) {
+            changed.push(event.locale.clone());
+        }
+    }
+
+    let Some(mut diagnostics) = diagnostics else {
+        return;
+    };
+    for locale in changed {
+        let path = missing_translations_diagnostic(&locale);
+        if diagnostics.get(&path).is_none() {
+            diagnostics.add(Diagnostic::new(path.clone()).with_suffix(" keys"));
+        }
+        if let Some(diagnostic) = diagnostics.get_mut(&path) {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value: missing.count(&locale) as f64,
+            });
         }
     }
 }
@@ -95,8 +395,10 @@ fn load_dynamic_fonts(
 ) {
     for dyn_font in FONT_FAMILIES.iter() {
         bevy::log::debug!("Loading dynamic font family: {}", dyn_font.family);
-        let mut font_folder = FontFolder::default();
-        font_folder.fallback = asset_server.load(Path::new(dyn_font.path).join("fallback.ttf"));
+        let mut font_folder = FontFolder {
+            fallback: asset_server.load(Path::new(dyn_font.path).join("fallback.ttf")),
+            ..Default::default()
+        };
         for font in dyn_font.locales.iter() {
             bevy::log::debug!("Loading font: {}", font);
             let locale = font.split('.').next().expect("Locale is required");
@@ -126,3 +428,26 @@ fn monitor_font_loading(
     commands.remove_resource::<FontsLoading>();
     bevy::log::debug!("All fonts loaded");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locales_are_validated_once_every_file_is_loaded() {
+        let mut locale_files = Assets::<LocaleFile>::default();
+        let en =
+            locale_files.add(LocaleFile::parse(r#"{"hello": "Hello"}"#, "json", "en").unwrap());
+        let fr = locale_files.reserve_handle();
+        let locale_manager = LocaleManager {
+            files: vec![en, fr.clone()],
+        };
+        assert!(!all_loaded(&locale_manager, &locale_files));
+
+        locale_files.insert(
+            &fr,
+            LocaleFile::parse(r#"{"hello": "Bonjour"}"#, "json", "fr").unwrap(),
+        );
+        assert!(all_loaded(&locale_manager, &locale_files));
+    }
+}
@@ -1,14 +1,29 @@
 use bevy::{
     asset::Handle,
-    ecs::{reflect::ReflectResource, system::Resource},
+    ecs::{
+        component::Component, entity::Entity, reflect::ReflectResource, system::Resource,
+        world::DeferredWorld,
+    },
     reflect::Reflect,
     text::Font,
-    utils::hashbrown::HashMap,
+    utils::hashbrown::{HashMap, HashSet},
 };
 use icu_locid::Locale;
+use std::{any::TypeId, sync::Mutex};
+
+use crate::{
+    assets::LocaleFile,
+    backend::{LocaleFileBackend, TranslationBackend},
+    components::I18nComponent,
+};
 
 /// Resource for managing the current locale and getting the available locales
 ///
+/// Translations are provided by its [TranslationBackend], [LocaleFileBackend] by default
+///
+/// Missing translations, number formats and fonts are resolved through the fallback chain of the
+/// locale, see [I18n::fallback_chain]
+///
 /// # Example
 /// ```
 /// use bevy::prelude::*;
@@ -17,21 +32,64 @@ use icu_locid::Locale;
 /// fn update_locale(mut i18n_res: ResMut<I18n>) {
 ///     i18n_res.set_locale("en");
 /// }
+///
+/// fn configure_fallbacks(mut i18n_res: ResMut<I18n>) {
+///     i18n_res.set_fallbacks("pt-BR", ["pt-PT", "en"]);
+///     i18n_res.set_fallbacks("zh-HK", ["zh-TW", "en"]);
+///     i18n_res.set_fallback_locale("en");
+/// }
+/// ```
 #[derive(Debug, Resource, Reflect)]
 #[reflect(Resource)]
 pub struct I18n {
     locales: Vec<String>,
     current: String,
+    /// Explicit fallback chains, keyed by locale
+    fallbacks: HashMap<String, Vec<String>>,
+    /// Last locale of every fallback chain
+    fallback_locale: Option<String>,
+    /// What to display when a translation is missing
+    missing_policy: MissingTranslationPolicy,
+    /// Keys and locales of the missing translations, drained by the translation systems
+    #[reflect(ignore)]
+    missing: Mutex<Vec<(String, String)>>,
+    #[reflect(ignore)]
+    backend: BoxedBackend,
+    /// Incremented whenever the translations may change for any locale, not only for the current one
+    #[reflect(ignore)]
+    revision: u64,
+    /// Set when the backend is replaced, until the plugin passes it the loaded locale files
+    #[reflect(ignore)]
+    backend_replaced: bool,
 }
 
 impl I18n {
+    /// Creates a new `I18n` resource with a custom [TranslationBackend]
+    ///
+    /// Insert it before adding the [crate::plugin::I18nPlugin] to replace the default backend
+    pub fn new(backend: impl TranslationBackend) -> Self {
+        let mut i18n = Self {
+            locales: vec![],
+            current: "en".to_string(),
+            fallbacks: HashMap::new(),
+            fallback_locale: None,
+            missing_policy: MissingTranslationPolicy::default(),
+            missing: Mutex::default(),
+            backend: BoxedBackend(Box::new(backend)),
+            revision: 0,
+            backend_replaced: false,
+        };
+        i18n.refresh_locales();
+        i18n
+    }
+
     pub fn set_locale(&mut self, locale: impl Into<String>) {
         let next_locale: String = locale.into();
         if let Err(err) = next_locale.parse::<Locale>() {
             bevy::log::error!("Invalid locale: {}", err);
             return;
         }
-        rust_i18n::set_locale(&next_locale);
+        self.backend.0.set_locale(&next_locale);
         bevy::log::debug!("Locale changed from {} to {}", self.current, next_locale);
         self.current = next_locale;
     }
@@ -40,32 +98,233 @@ impl I18n {
         &self.current
     }
 
+    /// Returns the locales that have translations in the [TranslationBackend]
+    ///
+    /// With the default backend, this list is empty until the first locale file has been loaded
     pub fn locales(&self) -> &[String] {
         &self.locales
     }
+
+    /// Sets the locales to try, in order, when a translation is missing for the provided locale
+    ///
+    /// Replaces the default chain of the locale, `pt-BR` -> `pt`
+    pub fn set_fallbacks<S: Into<String>>(
+        &mut self,
+        locale: impl Into<String>,
+        fallbacks: impl IntoIterator<Item = S>,
+    ) {
+        let locale: String = locale.into();
+        let fallbacks: Vec<String> = fallbacks.into_iter().map(Into::into).collect();
+        bevy::log::debug!("Fallbacks of {} locale set to {:?}", locale, fallbacks);
+        self.fallbacks.insert(locale, fallbacks);
+        self.revision += 1;
+    }
+
+    /// Sets the locale that ends every fallback chain
+    pub fn set_fallback_locale(&mut self, locale: impl Into<String>) {
+        self.fallback_locale = Some(locale.into());
+        self.revision += 1;
+    }
+
+    /// Removes the locale that ends every fallback chain
+    pub fn clear_fallback_locale(&mut self) {
+        self.fallback_locale = None;
+        self.revision += 1;
+    }
+
+    /// Returns the locales to try, in order, for the provided locale
+    ///
+    /// The chain starts with the locale itself, followed by its fallbacks set with [I18n::set_fallbacks].
+    /// Without explicit fallbacks, the locale is split at the last `-` until nothing is left, `en-US` -> `en`.
+    /// The chain ends with the fallback locale, if any
+    pub fn fallback_chain(&self, locale: &str) -> Vec<String> {
+        let mut chain = vec![locale.to_string()];
+        let mut push = |locale: &str| {
+            if !chain.iter().any(|l| l == locale) {
+                chain.push(locale.to_string());
+            }
+        };
+        if let Some(fallbacks) = self.fallbacks.get(locale) {
+            fallbacks.iter().for_each(|fallback| push(fallback));
+        } else {
+            let mut locale = locale;
+            while let Some(index) = locale.rfind('-') {
+                locale = &locale[..index];
+                push(locale);
+            }
+        }
+        if let Some(fallback_locale) = self.fallback_locale.as_ref() {
+            push(fallback_locale);
+        }
+        chain
+    }
+
+    /// Returns the first locale of the fallback chain with translations, or the locale itself if none have any
+    ///
+    /// Used to format numbers that aren't part of a translation, e.g. [crate::components::I18nNumber]
+    pub fn resolve_locale(&self, locale: &str) -> String {
+        self.fallback_chain(locale)
+            .into_iter()
+            .find(|locale| self.locales.contains(locale))
+            .unwrap_or_else(|| locale.to_string())
+    }
+
+    /// Returns what is displayed when a translation is missing
+    pub fn missing_policy(&self) -> MissingTranslationPolicy {
+        self.missing_policy
+    }
+
+    /// Sets what is displayed when a translation is missing
+    pub fn set_missing_policy(&mut self, policy: MissingTranslationPolicy) {
+        self.missing_policy = policy;
+        self.revision += 1;
+    }
+
+    /// Records a missing translation, so it can be reported with a [crate::events::MissingTranslation] event
+    pub(crate) fn report_missing(&self, key: &str, locale: &str) {
+        self.missing
+            .lock()
+            .expect("Missing translations lock is poisoned")
+            .push((key.to_string(), locale.to_string()));
+    }
+
+    /// Returns and clears the keys and locales of the missing translations recorded since the last call
+    pub(crate) fn take_missing(&self) -> Vec<(String, String)> {
+        std::mem::take(
+            &mut *self
+                .missing
+                .lock()
+                .expect("Missing translations lock is poisoned"),
+        )
+    }
+
+    /// Returns the [TranslationBackend] used for every translation
+    pub fn backend(&self) -> &dyn TranslationBackend {
+        self.backend.0.as_ref()
+    }
+
+    /// Returns the [TranslationBackend] mutably
+    ///
+    /// Call [I18n::refresh_locales] afterward if the available locales changed
+    pub fn backend_mut(&mut self) -> &mut dyn TranslationBackend {
+        self.revision += 1;
+        self.backend.0.as_mut()
+    }
+
+    /// Replaces the [TranslationBackend], keeping the current locale
+    ///
+    /// The [crate::plugin::I18nPlugin] passes the loaded locale files to the new backend in the next update,
+    /// before every registered component is re-translated with it
+    pub fn set_backend(&mut self, backend: impl TranslationBackend) {
+        self.backend = BoxedBackend(Box::new(backend));
+        self.backend.0.set_locale(&self.current);
+        self.backend_replaced = true;
+        self.refresh_locales();
+    }
+
+    /// Returns `true` if the backend has been replaced and hasn't been passed the loaded locale files yet
+    pub(crate) fn backend_replaced(&self) -> bool {
+        self.backend_replaced
+    }
+
+    /// Passes the locale files to the [TranslationBackend] and updates the available locales
+    pub(crate) fn load_locale_files(&mut self, files: &[&LocaleFile]) {
+        self.backend_mut().load_locale_files(files);
+        self.backend_replaced = false;
+        self.refresh_locales();
+    }
+
+    /// Updates the available locales from the [TranslationBackend]
+    pub fn refresh_locales(&mut self) {
+        self.locales = self.backend.0.locales();
+        self.revision += 1;
+        bevy::log::debug!("Available locales: {:?}", self.locales);
+    }
+
+    /// Returns a number that changes whenever the translations may change for any locale, e.g. when locale
+    /// files are loaded or the fallbacks are set, but not when the current locale changes
+    ///
+    /// Components with a fixed locale only need to be translated again when it changes
+    pub(crate) fn revision(&self) -> u64 {
+        self.revision
+    }
 }
 
 impl Default for I18n {
     fn default() -> Self {
-        Self {
-            current: rust_i18n::locale().to_string(),
-            locales: rust_i18n::available_locales!()
-                .into_iter()
-                .map(|s| s.into())
-                .collect(),
-        }
+        Self::new(LocaleFileBackend::default())
     }
 }
 
-/// Internal struct for managing fonts for a specific font family.
+/// What is displayed in place of a missing translation
 ///
-/// It attempts to find a specified font for the most specific locale.
+/// Every missing translation is reported with a [crate::events::MissingTranslation] event, whatever the policy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum MissingTranslationPolicy {
+    /// Display `{locale}.{key}`, e.g. `ja.messages.hello`
+    ShowKey,
+    /// Display the translation of the next locale of the fallback chain that has one,
+    /// `{locale}.{key}` if none do
+    #[default]
+    ShowFallback,
+    /// Display a placeholder that stands out, e.g. `[MISSING ja: messages.hello]`
+    Placeholder,
+}
+
+impl MissingTranslationPolicy {
+    /// Returns the text displayed for a missing translation, `fallback` is the translation of the next
+    /// locale of the fallback chain, if any
+    pub(crate) fn display(&self, locale: &str, key: &str, fallback: Option<String>) -> String {
+        match (self, fallback) {
+            (MissingTranslationPolicy::ShowFallback, Some(fallback)) => fallback,
+            (MissingTranslationPolicy::Placeholder, _) => format!("[MISSING {}: {}]", locale, key),
+            _ => format!("{}.{}", locale, key),
+        }
+    }
+}
+
+/// Resource counting the distinct missing translation keys reported for every locale since startup
 ///
-/// If unsuccessful, it will split the locale at the last `-` and try again.
+/// Updated from the [crate::events::MissingTranslation] events, the counts are also exposed as Bevy diagnostics
+/// when the `DiagnosticsPlugin` is added, see [crate::events::missing_translations_diagnostic]
+#[derive(Debug, Reflect, Default, Resource)]
+#[reflect(Resource)]
+pub struct MissingTranslations {
+    pub(crate) keys: HashMap<String, HashSet<String>>,
+}
+
+impl MissingTranslations {
+    /// Returns the number of distinct missing keys of a locale
+    pub fn count(&self, locale: &str) -> usize {
+        self.keys.get(locale).map_or(0, HashSet::len)
+    }
+
+    /// Returns the distinct missing keys of every locale
+    pub fn keys(&self) -> &HashMap<String, HashSet<String>> {
+        &self.keys
+    }
+}
+
+/// Boxed [TranslationBackend], so the [I18n] resource can still derive `Debug` and `Reflect`
+pub(crate) struct BoxedBackend(pub(crate) Box<dyn TranslationBackend>);
+
+impl Default for BoxedBackend {
+    fn default() -> Self {
+        Self(Box::new(LocaleFileBackend::default()))
+    }
+}
+
+impl std::fmt::Debug for BoxedBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BoxedBackend").finish_non_exhaustive()
+    }
+}
+
+/// Internal struct for managing fonts for a specific font family.
 ///
-/// `en-US` -> `en` -> `fallback`
+/// It attempts to find a specified font for each locale of the fallback chain, see [I18n::fallback_chain]
 ///
-/// If still unsuccessful, it will return the fallback font.
+/// If unsuccessful, it will return the fallback font.
 #[derive(Debug, Default, Reflect)]
 pub(crate) struct FontFolder {
     pub(crate) fallback: Handle<Font>,
@@ -73,22 +332,14 @@ pub(crate) struct FontFolder {
 }
 
 impl FontFolder {
-    pub(crate) fn get(&self, locale: impl Into<String>) -> Handle<Font> {
-        let locale: String = locale.into();
-        let mut locale = locale.as_str();
-
-        bevy::log::debug!("Evaluating font for {} locale", locale);
-        while !locale.is_empty() {
+    pub(crate) fn get(&self, chain: &[String]) -> Handle<Font> {
+        for locale in chain {
+            bevy::log::debug!("Evaluating font for {} locale", locale);
             if let Some(font) = self.fonts.get(locale) {
                 bevy::log::debug!("Font for {} locale found", locale);
                 return font.clone();
             }
-            if let Some(index) = locale.rfind('-') {
-                bevy::log::debug!("Font for {} locale was not found", locale);
-                locale = &locale[..index];
-            } else {
-                break;
-            }
+            bevy::log::debug!("Font for {} locale was not found", locale);
         }
 
         bevy::log::debug!("Returning the fallback font");
@@ -110,10 +361,11 @@ impl FontManager {
         self.fonts.insert(family, font_folder);
     }
 
-    pub(crate) fn get(&self, family: &str, locale: String) -> Handle<Font> {
+    /// Returns the font of the family for the first locale of the fallback chain that has one
+    pub(crate) fn get(&self, family: &str, chain: &[String]) -> Handle<Font> {
         if let Some(folder) = self.fonts.get(family) {
             bevy::log::debug!("Found font family: {}", family);
-            folder.get(locale)
+            folder.get(chain)
         } else {
             bevy::log::debug!("Font {} was not found, using default", family);
             Handle::<Font>::default()
@@ -121,7 +373,153 @@ impl FontManager {
     }
 }
 
+/// Returns the locale of a component of the entity, if it has one
+type ComponentLocale = fn(&DeferredWorld, Entity, &I18n) -> Option<String>;
+
+/// Resource holding the i18n components registered with
+/// [crate::plugin::I18nComponentRegistration::register_i18n_component]
+///
+/// Used by [crate::components::I18nFont] to pick the font of the locale of the component of its entity
+#[derive(Default, Resource)]
+pub(crate) struct I18nComponents {
+    locales: Vec<(TypeId, ComponentLocale)>,
+}
+
+impl I18nComponents {
+    pub(crate) fn register<T: I18nComponent + Component>(&mut self) {
+        if self.locales.iter().all(|(id, _)| *id != TypeId::of::<T>()) {
+            self.locales
+                .push((TypeId::of::<T>(), component_locale::<T>));
+        }
+    }
+
+    /// Returns the locale of the first registered component of the entity, in registration order
+    pub(crate) fn locale(
+        &self,
+        world: &DeferredWorld,
+        entity: Entity,
+        i18n: &I18n,
+    ) -> Option<String> {
+        self.locales
+            .iter()
+            .find_map(|(_, locale)| locale(world, entity, i18n))
+    }
+}
+
+fn component_locale<T: I18nComponent + Component>(
+    world: &DeferredWorld,
+    entity: Entity,
+    i18n: &I18n,
+) -> Option<String> {
+    world
+        .get::<T>(entity)
+        .map(|component| component.locale(i18n))
+}
+
+/// Resource holding the handles of the locale files found by the build script
+///
+/// Keeps the [LocaleFile] assets alive for the lifetime of the app
+#[derive(Debug, Reflect, Default, Resource)]
+#[reflect(Resource)]
+pub(crate) struct LocaleManager {
+    pub(crate) files: Vec<Handle<LocaleFile>>,
+}
+
 /// Hacky resource to signal that fonts are still loading
 #[derive(Debug, Reflect, Default, Resource)]
 #[reflect(Resource)]
 pub(crate) struct FontsLoading;
+
+#[cfg(test)]
+mod tests {
+    use bevy::text::TextSpan;
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Badge(&'static str);
+
+    impl I18nComponent for Badge {
+        type Target = TextSpan;
+
+        fn locale(&self, _i18n: &I18n) -> String {
+            self.0.to_string()
+        }
+
+        fn translate(&self, _i18n: &I18n) -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn locales_of_registered_components_are_resolved() {
+        let mut world = bevy::ecs::world::World::new();
+        let badge = world.spawn(Badge("ja")).id();
+        let other = world.spawn_empty().id();
+        let i18n = I18n::default();
+
+        let mut components = I18nComponents::default();
+        components.register::<Badge>();
+        components.register::<Badge>();
+        assert_eq!(components.locales.len(), 1);
+
+        let world = DeferredWorld::from(&mut world);
+        assert_eq!(
+            components.locale(&world, badge, &i18n),
+            Some("ja".to_string())
+        );
+        assert_eq!(components.locale(&world, other, &i18n), None);
+    }
+
+    #[test]
+    fn missing_translations_are_displayed_with_the_policy() {
+        let display = |policy: MissingTranslationPolicy, fallback: Option<&str>| {
+            policy.display("fr", "hello", fallback.map(str::to_string))
+        };
+        assert_eq!(
+            display(MissingTranslationPolicy::ShowKey, Some("Hello")),
+            "fr.hello"
+        );
+        assert_eq!(
+            display(MissingTranslationPolicy::ShowFallback, Some("Hello")),
+            "Hello"
+        );
+        assert_eq!(
+            display(MissingTranslationPolicy::ShowFallback, None),
+            "fr.hello"
+        );
+        assert_eq!(
+            display(MissingTranslationPolicy::Placeholder, Some("Hello")),
+            "[MISSING fr: hello]"
+        );
+    }
+
+    #[test]
+    fn missing_translations_are_taken_once() {
+        let i18n = I18n::default();
+        i18n.report_missing("hello", "fr");
+        i18n.report_missing("bye", "ja");
+        assert_eq!(
+            i18n.take_missing(),
+            [
+                ("hello".to_string(), "fr".to_string()),
+                ("bye".to_string(), "ja".to_string())
+            ]
+        );
+        assert!(i18n.take_missing().is_empty());
+    }
+
+    #[test]
+    fn replaced_backends_wait_for_the_locale_files() {
+        let mut i18n = I18n::default();
+        let revision = i18n.revision();
+        i18n.set_backend(LocaleFileBackend::default());
+        assert!(i18n.backend_replaced());
+        assert!(i18n.revision() > revision);
+
+        let file = LocaleFile::parse(r#"{"hello": "Hello"}"#, "json", "en").unwrap();
+        i18n.load_locale_files(&[&file]);
+        assert!(!i18n.backend_replaced());
+        assert_eq!(i18n.locales(), ["en"]);
+    }
+}
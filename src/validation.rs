@@ -0,0 +1,327 @@
+use std::fmt;
+
+use bevy::{
+    ecs::{reflect::ReflectResource, system::Resource},
+    reflect::Reflect,
+    utils::hashbrown::HashSet,
+};
+
+use crate::backend::TranslationBackend;
+
+/// Plural categories used as key suffixes, `messages.cats.one`
+const PLURAL_SUFFIXES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+/// Resource enabling the validation of every locale against a base locale
+///
+/// When inserted, the coverage of the locales is validated with [validate_locales] whenever the locale
+/// files are loaded or reloaded, and every issue is logged as a warning
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_simple_i18n::prelude::*;
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(I18nPlugin)
+///     .insert_resource(LocaleValidation::new("en"));
+/// ```
+#[derive(Debug, Clone, Reflect, Resource)]
+#[reflect(Resource)]
+pub struct LocaleValidation {
+    /// Locale every other locale is compared with
+    pub base_locale: String,
+}
+
+impl LocaleValidation {
+    /// Creates a new `LocaleValidation` resource with the provided base locale
+    pub fn new(base_locale: impl Into<String>) -> Self {
+        Self {
+            base_locale: base_locale.into(),
+        }
+    }
+}
+
+/// Placeholders of a key that differ from the base locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderMismatch {
+    pub key: String,
+    /// Sorted `%{placeholder}` names of the base locale
+    pub expected: Vec<String>,
+    /// Sorted `%{placeholder}` names of the validated locale
+    pub found: Vec<String>,
+}
+
+/// Coverage issues of a locale compared with the base locale
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocaleReport {
+    pub locale: String,
+    /// Keys of the base locale that are missing, sorted
+    pub missing_keys: Vec<String>,
+    /// Keys that don't exist in the base locale, sorted
+    pub extra_keys: Vec<String>,
+    /// Keys whose `%{placeholder}` names differ from the base locale, sorted by key
+    pub mismatched_placeholders: Vec<PlaceholderMismatch>,
+}
+
+impl LocaleReport {
+    /// Returns true if the locale has the same keys and placeholders as the base locale
+    pub fn is_ok(&self) -> bool {
+        self.missing_keys.is_empty()
+            && self.extra_keys.is_empty()
+            && self.mismatched_placeholders.is_empty()
+    }
+}
+
+impl fmt::Display for LocaleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Locale {}:", self.locale)?;
+        if self.is_ok() {
+            return write!(f, " ok");
+        }
+        for key in self.missing_keys.iter() {
+            write!(f, "\n  missing key: {}", key)?;
+        }
+        for key in self.extra_keys.iter() {
+            write!(f, "\n  extra key: {}", key)?;
+        }
+        for mismatch in self.mismatched_placeholders.iter() {
+            write!(
+                f,
+                "\n  mismatched placeholders for {}: expected {:?}, found {:?}",
+                mismatch.key, mismatch.expected, mismatch.found
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the keys and `%{placeholder}` sets of every locale of the backend with the base locale
+///
+/// Plural forms are compared by their parent key, since locales use different plural categories:
+/// `messages.cats.few` is neither missing in `en` nor extra in `ru` as long as both have `messages.cats.other`
+///
+/// Only the keys listed by [TranslationBackend::keys] are validated. Fluent messages are validated by their
+/// message and attribute keys, `menu` and `menu.title`, and their `{ $name }` variables are compared like placeholders
+///
+/// Can also be called from a build script or a test with a [crate::backend::LocaleFileBackend] built from
+/// [crate::assets::LocaleFile::parse], to catch the issues before the game is run
+///
+/// # Example
+///
+/// ```
+/// use bevy_simple_i18n::prelude::*;
+///
+/// let mut backend = LocaleFileBackend::default();
+/// backend.extend(&LocaleFile::parse(r#"{ "hello": "Hello, %{name}!", "bye": "Bye" }"#, "json", "en").unwrap());
+/// backend.extend(&LocaleFile::parse(r#"{ "hello": "こんにちは" }"#, "json", "ja").unwrap());
+///
+/// let reports = validate_locales(&backend, "en");
+/// assert_eq!(reports[0].locale, "ja");
+/// assert_eq!(reports[0].missing_keys, ["bye"]);
+/// assert_eq!(reports[0].mismatched_placeholders[0].expected, ["name"]);
+/// ```
+pub fn validate_locales(backend: &dyn TranslationBackend, base_locale: &str) -> Vec<LocaleReport> {
+    let base_keys: HashSet<String> = backend.keys(base_locale).into_iter().collect();
+    backend
+        .locales()
+        .into_iter()
+        .filter(|locale| locale != base_locale)
+        .map(|locale| {
+            let keys: HashSet<String> = backend.keys(&locale).into_iter().collect();
+
+            let mut missing_keys: Vec<String> = base_keys
+                .iter()
+                .filter(|key| !keys.contains(*key) && !has_plural_sibling(key, &keys))
+                .cloned()
+                .collect();
+            missing_keys.sort();
+            let mut extra_keys: Vec<String> = keys
+                .iter()
+                .filter(|key| !base_keys.contains(*key) && !has_plural_sibling(key, &base_keys))
+                .cloned()
+                .collect();
+            extra_keys.sort();
+
+            let mut mismatched_placeholders: Vec<PlaceholderMismatch> = keys
+                .iter()
+                .filter_map(|key| {
+                    let base_key = if base_keys.contains(key) {
+                        key.clone()
+                    } else {
+                        plural_parent(key).map(|parent| format!("{}.other", parent))?
+                    };
+                    let expected = placeholders(&backend.translate(base_locale, &base_key, &[])?);
+                    let found = placeholders(&backend.translate(&locale, key, &[])?);
+                    (expected != found).then(|| PlaceholderMismatch {
+                        key: key.clone(),
+                        expected,
+                        found,
+                    })
+                })
+                .collect();
+            mismatched_placeholders.sort_by(|a, b| a.key.cmp(&b.key));
+
+            LocaleReport {
+                locale,
+                missing_keys,
+                extra_keys,
+                mismatched_placeholders,
+            }
+        })
+        .collect()
+}
+
+/// Returns the parent of a plural key, `messages.cats.one` -> `messages.cats`
+fn plural_parent(key: &str) -> Option<&str> {
+    let (parent, suffix) = key.rsplit_once('.')?;
+    PLURAL_SUFFIXES.contains(&suffix).then_some(parent)
+}
+
+/// Returns true if the key is a plural form and the other keys have the `other` form of its parent
+fn has_plural_sibling(key: &str, keys: &HashSet<String>) -> bool {
+    plural_parent(key).is_some_and(|parent| keys.contains(&format!("{}.other", parent)))
+}
+
+/// Returns the sorted and deduplicated `%{placeholder}` names of a message
+///
+/// Fluent messages are formatted without arguments, so their variables are displayed as `{$name}` and
+/// collected as well
+fn placeholders(message: &str) -> Vec<String> {
+    let mut names = vec![];
+    for prefix in ["%{", "{$"] {
+        let mut rest = message;
+        while let Some(start) = rest.find(prefix) {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find('}') else {
+                break;
+            };
+            names.push(after[..end].to_string());
+            rest = &after[end + 1..];
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assets::LocaleFile, backend::LocaleFileBackend};
+
+    fn backend(files: &[(&str, &str)]) -> LocaleFileBackend {
+        let mut backend = LocaleFileBackend::default();
+        for (locale, json) in files {
+            backend.extend(&LocaleFile::parse(json, "json", locale).unwrap());
+        }
+        backend
+    }
+
+    #[test]
+    fn missing_and_extra_keys_are_reported() {
+        let backend = backend(&[
+            (
+                "en",
+                r#"{ "hello": "Hello", "menu": { "start": "Start", "quit": "Quit" } }"#,
+            ),
+            (
+                "fr",
+                r#"{ "hello": "Bonjour", "menu": { "start": "Jouer", "options": "Options" } }"#,
+            ),
+            (
+                "ja",
+                r#"{ "hello": "こんにちは", "menu": { "start": "スタート", "quit": "終了" } }"#,
+            ),
+        ]);
+        let reports = validate_locales(&backend, "en");
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].locale, "fr");
+        assert_eq!(reports[0].missing_keys, ["menu.quit"]);
+        assert_eq!(reports[0].extra_keys, ["menu.options"]);
+        assert!(!reports[0].is_ok());
+        assert_eq!(reports[1].locale, "ja");
+        assert!(reports[1].is_ok());
+        assert_eq!(reports[1].to_string(), "Locale ja: ok");
+    }
+
+    #[test]
+    fn plural_forms_are_compared_by_parent_key() {
+        let backend = backend(&[
+            (
+                "en",
+                r#"{ "cats": { "one": "%{count} cat", "other": "%{count} cats" }, "dogs": { "one": "a dog", "other": "dogs" } }"#,
+            ),
+            (
+                "pl",
+                r#"{ "cats": { "one": "%{count} kot", "few": "%{count} koty", "many": "%{count} kotów", "other": "%{count} kota" }, "dogs": { "few": "psy" } }"#,
+            ),
+        ]);
+        let report = &validate_locales(&backend, "en")[0];
+        assert_eq!(report.missing_keys, ["dogs.one", "dogs.other"]);
+        // `dogs.few` only needs `dogs.other` in the base locale
+        assert!(report.extra_keys.is_empty());
+        assert!(report.mismatched_placeholders.is_empty());
+    }
+
+    #[test]
+    fn mismatched_placeholders_are_reported() {
+        let backend = backend(&[
+            (
+                "en",
+                r#"{ "greet": "Hello, %{name}! %{name}", "cats": { "one": "%{count} cat", "other": "%{count} cats" } }"#,
+            ),
+            (
+                "de",
+                r#"{ "greet": "Hallo, %{nme}!", "cats": { "one": "eine Katze", "other": "%{count} Katzen" } }"#,
+            ),
+        ]);
+        let report = &validate_locales(&backend, "en")[0];
+        assert_eq!(
+            report.mismatched_placeholders,
+            [
+                PlaceholderMismatch {
+                    key: "cats.one".to_string(),
+                    expected: vec!["count".to_string()],
+                    found: vec![],
+                },
+                PlaceholderMismatch {
+                    key: "greet".to_string(),
+                    expected: vec!["name".to_string()],
+                    found: vec!["nme".to_string()],
+                },
+            ]
+        );
+        assert_eq!(
+            report.to_string(),
+            "Locale de:\n  mismatched placeholders for cats.one: expected [\"count\"], found []\n  mismatched placeholders for greet: expected [\"name\"], found [\"nme\"]"
+        );
+    }
+
+    #[cfg(feature = "fluent")]
+    #[test]
+    fn fluent_messages_are_validated() {
+        let mut backend = backend(&[("en", r#"{ "hello": "Hello, %{name}!", "bye": "Bye" }"#)]);
+        backend.extend(
+            &LocaleFile::parse(
+                "hello = Bonjour, { $nom } !\nmenu = Menu\n    .title = Titre\n",
+                "ftl",
+                "fr",
+            )
+            .unwrap(),
+        );
+        let report = &validate_locales(&backend, "en")[0];
+        assert_eq!(report.locale, "fr");
+        assert_eq!(report.missing_keys, ["bye"]);
+        assert_eq!(report.extra_keys, ["menu", "menu.title"]);
+        assert_eq!(
+            report.mismatched_placeholders,
+            [PlaceholderMismatch {
+                key: "hello".to_string(),
+                expected: vec!["name".to_string()],
+                found: vec!["nom".to_string()],
+            }]
+        );
+    }
+}
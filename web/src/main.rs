@@ -27,12 +27,18 @@ fn main() {
         )
         .add_plugins(I18nPlugin)
         .add_systems(Startup, setup)
-        .add_systems(Update, button_system)
+        .add_systems(
+            Update,
+            (
+                button_system,
+                spawn_locale_buttons.run_if(resource_changed::<I18n>),
+            ),
+        )
         .run();
 }
 
-fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
-    commands.spawn(Camera2d::default());
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
     commands
         .spawn(Node {
             width: Val::Percent(100.),
@@ -101,6 +107,40 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     ));
                 });
 
+            // Example that shows pluralization, the `count` argument selects the plural form
+            for count in [1, 3, 5] {
+                parent
+                    .spawn(Node {
+                        display: Display::Flex,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(Text::new(format!("'messages.apples' ({}) => ", count)));
+                        parent.spawn((
+                            I18nText::new("messages.apples").with_num_arg("count", count),
+                            I18nFont::new("NotoSans"),
+                        ));
+                    });
+            }
+
+            // Example that shows an ICU MessageFormat translation
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(Text::new("'messages.inbox' (MessageFormat) => "));
+                    parent.spawn((
+                        I18nText::new("messages.inbox")
+                            .with_num_arg("count", 3)
+                            .with_message_format(),
+                        I18nFont::new("NotoSans"),
+                    ));
+                });
+
             // Spawns a node with a text that will always be in Japanese
             parent
                 .spawn(Node {
@@ -116,8 +156,10 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     ));
                 });
 
-            parent
-                .spawn(Node {
+            // Locale buttons are spawned once the locale files are loaded
+            parent.spawn((
+                LocaleButtons,
+                Node {
                     display: Display::Flex,
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
@@ -125,49 +167,67 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     row_gap: Val::Px(10.),
                     column_gap: Val::Px(10.),
                     ..default()
-                })
-                .with_children(|parent| {
-                    for locale in i18n_res.locales() {
-                        parent
-                            .spawn((
-                                Button,
-                                Node {
-                                    min_width: Val::Px(200.0),
-                                    padding: UiRect::all(Val::Px(10.0)),
-                                    border: UiRect::all(Val::Px(5.0)),
-                                    justify_content: JustifyContent::Center,
-                                    align_items: AlignItems::Center,
-                                    ..default()
-                                },
-                                BorderColor(Color::BLACK),
-                                BorderRadius::MAX,
-                                BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
-                            ))
-                            .with_child((
-                                Text::new(locale),
-                                TextFont {
-                                    font_size: 50.0,
-                                    ..default()
-                                },
-                                TextColor(Color::srgb(0.9, 0.9, 0.9)),
-                            ));
-                    }
-                });
+                },
+            ));
         });
 }
 
+#[derive(Component)]
+struct LocaleButtons;
+
+/// Spawns a button for each locale, the locale files are loaded after the startup
+fn spawn_locale_buttons(
+    mut commands: Commands,
+    i18n_res: Res<I18n>,
+    mut spawned: Local<Vec<String>>,
+    container: Query<Entity, With<LocaleButtons>>,
+) {
+    let Ok(container) = container.get_single() else {
+        return;
+    };
+    commands.entity(container).with_children(|parent| {
+        for locale in i18n_res.locales() {
+            if spawned.contains(locale) {
+                continue;
+            }
+            spawned.push(locale.clone());
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        min_width: Val::Px(200.0),
+                        padding: UiRect::all(Val::Px(10.0)),
+                        border: UiRect::all(Val::Px(5.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BorderColor(Color::BLACK),
+                    BorderRadius::MAX,
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                ))
+                .with_child((
+                    Text::new(locale),
+                    TextFont {
+                        font_size: 50.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                ));
+        }
+    });
+}
+
+#[allow(clippy::type_complexity)]
 fn button_system(
     interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
     text_query: Query<&Text>,
     mut i18n_res: ResMut<I18n>,
 ) {
     for (interaction, children) in interaction_query.iter() {
-        match *interaction {
-            Interaction::Pressed => {
-                let text = text_query.get(children[0]).unwrap().clone().0;
-                i18n_res.set_locale(text);
-            }
-            _ => {}
+        if *interaction == Interaction::Pressed {
+            let text = text_query.get(children[0]).unwrap().clone().0;
+            i18n_res.set_locale(text);
         }
     }
 }